@@ -1,79 +1,278 @@
 use std::collections::HashMap;
+use std::env;
 use std::error::Error;
 use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use dirs::config_dir;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::*;
-use serde::Deserialize;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use serde::{Deserialize, Serialize};
 
-use crate::games::{GameAction, GameKind, GameState, StatRecord};
-use crate::hud::{self, HudContext};
-use crate::menu::MenuState;
+use crate::config::{self, Config, HistoryTrim};
+use crate::daily::{self, Daily, DailyBest};
+use crate::debug::{BenchSummary, DebugStats};
+use crate::errlog;
+use crate::export;
+use crate::games::typing_game::TypingState;
+use crate::games::{Game, GameAction, GameKind, Snapshot, StatRecord};
+use crate::hud::{self, HudContext, ToastKind};
+use crate::leaderboard;
+use crate::menu::{self, ChartStyle, MenuSort, MenuState};
+use crate::serve::StatsFeed;
+use crate::theme::{self, Palette};
+use crate::totals::{self, Totals};
 
-const TICK_RATE: Duration = Duration::from_millis(50);
-const HISTORY_LIMIT: usize = 64;
+const CLOCK_TICK: Duration = Duration::from_secs(1);
+/// Below this the HUD alone would crowd out the game, so only a notice is
+/// drawn until the terminal grows again.
+const MIN_WIDTH: u16 = 24;
+const MIN_HEIGHT: u16 = 9;
+/// Overrides where `scores.json` is read and written.
+const STATS_PATH_ENV: &str = "ARCADE_STATS_PATH";
+/// Two presses of Esc within this window leave the game for the menu.
+const DOUBLE_ESC: Duration = Duration::from_millis(400);
+/// Redraws timed by `:bench`; small enough that the UI only stalls for a
+/// moment.
+const BENCH_SAMPLES: usize = 50;
 
 pub struct App {
     menu: MenuState,
-    active: Option<GameState>,
+    active: Option<Box<dyn Game>>,
     stats: HashMap<GameKind, Vec<StatRecord>>,
+    unknown_stats: HashMap<String, Vec<StatRecord>>,
     should_quit: bool,
     toast: Option<Toast>,
     command: Option<CommandPalette>,
+    /// The last command line thrown away with Esc, for Ctrl-R to bring back.
+    last_discarded: Option<String>,
     stats_path: Option<PathBuf>,
+    config: Config,
+    config_path: Option<PathBuf>,
     show_help: bool,
+    show_scores: bool,
+    show_about: bool,
+    show_probe: bool,
+    /// Zen mode: the HUD is hidden unless a command is being typed or the
+    /// controls are shown with `:help`.
+    zen: bool,
+    /// Step of the tutorial overlay, when it is showing.
+    tutorial: Option<usize>,
+    session_start: Instant,
+    debug: Option<DebugStats>,
+    dirty: bool,
+    clock_second: u64,
+    practice: bool,
+    resume: Option<Snapshot>,
+    last_esc: Option<Instant>,
+    totals: Totals,
+    totals_path: Option<PathBuf>,
+    /// When the open game was launched, for the lifetime play time.
+    play_start: Option<Instant>,
+    /// The daily challenge in progress, if any.
+    daily: Option<Daily>,
+    /// Local HTTP stats feed from `--serve`.
+    feed: Option<StatsFeed>,
+    /// When the running game was paused because the terminal lost focus.
+    paused_at: Option<Instant>,
+    /// Text piped in at launch, so `:replay` restarts Typing on it.
+    typing_text: Option<String>,
 }
 
 impl Default for App {
     fn default() -> Self {
-        let (mut stats, stats_path) = load_persisted_stats();
-        for history in stats.values_mut() {
-            if history.len() > HISTORY_LIMIT {
-                let overflow = history.len() - HISTORY_LIMIT;
-                history.drain(0..overflow);
+        let (PersistedStats { mut stats, unknown }, stats_path, local_stats) =
+            load_persisted_stats();
+        let local_toast = local_stats.then(|| {
+            Toast::warn(format!(
+                "No config directory · scores kept in {} (set {STATS_PATH_ENV} to move them)",
+                stats_path.display()
+            ))
+        });
+        let mut config_path = config::config_path();
+        let (config, config_error) = match config_path.as_deref().map(Config::load) {
+            Some(Ok(config)) => (config, None),
+            Some(Err(err)) => {
+                // Leave a broken file for the user to fix rather than
+                // overwriting it with the defaults on the next save.
+                config_path = None;
+                errlog::append(&err);
+                (Config::default(), Some(Toast::error(err)))
             }
+            None => (Config::default(), None),
+        };
+        let unknown_toast = (!unknown.is_empty()).then(|| {
+            let mut keys: Vec<&str> = unknown.keys().map(String::as_str).collect();
+            keys.sort_unstable();
+            Toast::warn(format!(
+                "Kept scores for unknown games: {}",
+                keys.join(", ")
+            ))
+        });
+        for (kind, history) in stats.iter_mut() {
+            trim_history(*kind, history, &config);
         }
+        theme::set_palette(config.palette);
+        theme::set_ascii(config.ascii_only);
+        theme::set_dim(config.dim);
+        let resume = take_saved_session();
+        let resume_toast = resume.as_ref().map(|snapshot| {
+            Toast::new(format!(
+                "Unfinished {} run saved · :resume to continue",
+                snapshot.kind().title()
+            ))
+        });
+        let tutorial = opening_tutorial(&stats_path, &config);
+        let totals_path = totals::totals_path();
+        let totals = totals_path.as_deref().map(Totals::load).unwrap_or_default();
+        let mut menu = MenuState::default();
+        menu.set_favorites(config.favorites());
+        menu.set_sort(config.menu.sort, &stats, &totals);
+        menu.set_chart_style(config.menu.chart);
+        menu.set_list_percent(config.menu.list_percent);
         Self {
-            menu: MenuState::default(),
+            menu,
             active: None,
             stats,
+            unknown_stats: unknown,
             should_quit: false,
-            toast: None,
+            toast: config_error
+                .or(local_toast)
+                .or(unknown_toast)
+                .or(resume_toast),
             command: None,
-            stats_path,
+            last_discarded: None,
+            stats_path: Some(stats_path),
+            config,
+            config_path,
             show_help: false,
+            show_scores: false,
+            show_about: false,
+            show_probe: false,
+            zen: false,
+            tutorial,
+            session_start: Instant::now(),
+            debug: None,
+            dirty: true,
+            clock_second: 0,
+            practice: false,
+            resume,
+            last_esc: None,
+            totals,
+            totals_path,
+            play_start: None,
+            daily: None,
+            feed: None,
+            paused_at: None,
+            typing_text: None,
         }
     }
 }
 
 impl App {
+    /// Opens Typing on text piped in at launch, or on random words when
+    /// the pipe was empty.
+    pub fn start_typing_text(&mut self, text: &str) {
+        if text.trim().is_empty() {
+            self.set_active(GameKind::Typing.new_state(&self.config));
+            self.toast = Some(Toast::warn("stdin was empty · typing random words"));
+            return;
+        }
+        self.typing_text = Some(text.to_string());
+        self.set_active(Box::new(
+            TypingState::from_text(text).with_round_duration(self.config.typing_round()),
+        ));
+    }
+
+    pub fn serve(&mut self, feed: StatsFeed) {
+        feed.publish(&self.stats);
+        self.feed = Some(feed);
+    }
+
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
         let mut last_tick = Instant::now();
         while !self.should_quit {
-            terminal.draw(|frame| self.render(frame))?;
-            let timeout = TICK_RATE
+            if self.dirty {
+                let draw_start = Instant::now();
+                terminal.draw(|frame| self.render(frame))?;
+                if let Some(debug) = &mut self.debug {
+                    debug.record_render(draw_start.elapsed());
+                }
+                self.dirty = false;
+            }
+            let tick_rate = self.tick_rate();
+            let timeout = tick_rate
                 .checked_sub(last_tick.elapsed())
                 .unwrap_or_else(|| Duration::from_secs(0));
             if event::poll(timeout)? {
                 let evt = event::read()?;
                 self.handle_event(evt);
             }
-            if last_tick.elapsed() >= TICK_RATE {
+            if last_tick.elapsed() >= tick_rate {
                 self.on_tick();
                 last_tick = Instant::now();
             }
         }
+        self.save_session();
+        self.close_active();
         Ok(())
     }
 
+    fn hud_height(&self) -> u16 {
+        if self.zen && self.command.is_none() && !self.show_help {
+            0
+        } else {
+            4
+        }
+    }
+
+    /// The active game's preferred tick, capped so the HUD clock still
+    /// updates every second and the debug overlay sees the configured rate.
+    fn tick_rate(&self) -> Duration {
+        let base = self.config.tick_rate();
+        let ui = if self.debug.is_some() {
+            base
+        } else {
+            CLOCK_TICK
+        };
+        self.active
+            .as_ref()
+            .and_then(|game| game.desired_tick())
+            .unwrap_or(base)
+            .min(ui)
+    }
+
     fn handle_event(&mut self, event: Event) {
+        self.dirty = true;
+        if let Some(debug) = &mut self.debug {
+            debug.record_event(Instant::now());
+        }
         match &event {
             Event::Key(key) => self.handle_key(*key),
+            Event::Mouse(mouse)
+                if self.active.is_none()
+                    && !self.show_scores
+                    && !self.show_about
+                    && !self.show_probe
+                    && self.tutorial.is_none() =>
+            {
+                self.handle_menu_mouse(*mouse)
+            }
+            Event::Resize(width, height) => {
+                if let Some(active) = &mut self.active {
+                    active.on_resize(*width, *height);
+                }
+            }
+            // Terminals that never report focus simply never pause.
+            Event::FocusLost => self.pause(),
+            Event::FocusGained => self.unpause(),
             _ => {
                 if let Some(active) = &mut self.active {
                     let action = active.handle_event(&event);
@@ -84,10 +283,35 @@ impl App {
     }
 
     fn handle_key(&mut self, key: KeyEvent) {
+        // Any key also resumes, in case focus comes back unreported.
+        if self.paused_at.is_some() {
+            self.unpause();
+            return;
+        }
+        if let Some(step) = self.tutorial {
+            if key.code == KeyCode::Esc || step + 1 >= TUTORIAL.len() {
+                self.finish_tutorial();
+            } else {
+                self.tutorial = Some(step + 1);
+            }
+            return;
+        }
+
         if self.handle_command_key(key) {
             return;
         }
 
+        if self.show_about || self.show_probe {
+            self.show_about = false;
+            self.show_probe = false;
+            return;
+        }
+
+        if self.show_scores {
+            self.show_scores = false;
+            return;
+        }
+
         match key.code {
             KeyCode::Char(':') if key.modifiers.is_empty() => {
                 self.command = Some(CommandPalette::default());
@@ -96,7 +320,18 @@ impl App {
             _ => {}
         }
 
+        if self.is_double_esc(key) {
+            self.return_to_menu();
+            return;
+        }
+
         if let Some(active) = &mut self.active {
+            if key.code == KeyCode::Char('r')
+                && key.modifiers.is_empty()
+                && active.restart_finished()
+            {
+                return;
+            }
             let action = active.handle_event(&Event::Key(key));
             self.handle_game_action(action);
         } else {
@@ -104,25 +339,64 @@ impl App {
         }
     }
 
+    /// The first Esc still reaches the game, so games keep their own use of
+    /// it; a second one in quick succession always goes back to the menu.
+    fn is_double_esc(&mut self, key: KeyEvent) -> bool {
+        if self.active.is_none() || key.code != KeyCode::Esc {
+            self.last_esc = None;
+            return false;
+        }
+        let now = Instant::now();
+        if let Some(last) = self.last_esc.take()
+            && now.duration_since(last) <= DOUBLE_ESC
+        {
+            return true;
+        }
+        self.last_esc = Some(now);
+        false
+    }
+
+    fn finish_tutorial(&mut self) {
+        self.tutorial = None;
+        if !self.config.tutorial_seen {
+            self.config.tutorial_seen = true;
+            self.save_config();
+        }
+    }
+
+    fn return_to_menu(&mut self) {
+        self.close_active();
+        self.last_esc = None;
+        self.toast = Some(Toast::new("Returned to menu"));
+    }
+
     fn handle_command_key(&mut self, key: KeyEvent) -> bool {
         if let Some(command) = &mut self.command {
             match key.code {
                 KeyCode::Esc => {
+                    if !command.buffer.trim().is_empty() {
+                        self.last_discarded = Some(std::mem::take(&mut command.buffer));
+                    }
                     self.command = None;
                 }
+                KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    if let Some(buffer) = self.last_discarded.take() {
+                        command.caret = buffer.chars().count();
+                        command.buffer = buffer;
+                    }
+                }
                 KeyCode::Enter => {
                     let buffer = command.buffer.trim().to_string();
                     self.command = None;
                     self.execute_command(buffer);
                 }
-                KeyCode::Backspace => {
-                    command.buffer.pop();
-                }
-                KeyCode::Char(ch) => {
-                    if !ch.is_control() {
-                        command.buffer.push(ch);
-                    }
-                }
+                KeyCode::Backspace => command.backspace(),
+                KeyCode::Delete => command.delete(),
+                KeyCode::Left => command.left(),
+                KeyCode::Right => command.right(),
+                KeyCode::Home => command.caret = 0,
+                KeyCode::End => command.caret = command.buffer.chars().count(),
+                KeyCode::Char(ch) if !ch.is_control() => command.insert(ch),
                 _ => {}
             }
             true
@@ -136,93 +410,536 @@ impl App {
             "qa" | "quitall" => self.should_quit = true,
             "q" | "quit" => {
                 if self.active.is_some() {
-                    self.active = None;
-                    self.toast = Some(Toast::new("Returned to menu"));
+                    self.return_to_menu();
                 } else {
                     self.should_quit = true;
                 }
             }
-            "menu" => {
-                self.active = None;
-                self.toast = Some(Toast::new("Returned to menu"));
-            }
+            "menu" => self.return_to_menu(),
             "restart" => {
-                if let Some(kind) = self.active.as_ref().map(GameState::kind) {
-                    self.active = Some(GameState::new(kind));
+                if let Some(kind) = self.active.as_ref().map(|game| game.kind()) {
+                    self.set_active(kind.new_state(&self.config));
                     self.toast = Some(Toast::new(format!("Restarted {}", kind.title())));
                 }
             }
+            "replay" => self.replay(),
+            "resume" => match self.resume.take() {
+                Some(snapshot) => {
+                    let kind = snapshot.kind();
+                    self.set_active(snapshot.restore(&self.config));
+                    self.toast = Some(Toast::new(format!("Resumed {}", kind.title())));
+                }
+                None => self.toast = Some(Toast::warn("No saved run to resume")),
+            },
+            other if other.starts_with("export-json ") => self.export_json(&other[12..]),
+            "seed" => self.report_seed(),
+            "play" => self.toast = Some(Toast::error("Usage: :play <game>, e.g. :play aim")),
+            other if other.starts_with("play ") => self.play(&other[5..]),
+            other if other.starts_with("seed ") => self.restart_with_seed(&other[5..]),
+            "note" => self.annotate_last_record(""),
+            other if other.starts_with("note ") => self.annotate_last_record(&other[5..]),
+            "scores" => self.show_scores = true,
+            "about" | "version" => self.show_about = true,
+            "probe" => self.show_probe = true,
+            "dim" => {
+                self.config.dim = !self.config.dim;
+                theme::set_dim(self.config.dim);
+                self.save_config();
+                self.toast = Some(Toast::new(if self.config.dim {
+                    "Dim mode on"
+                } else {
+                    "Dim mode off"
+                }));
+            }
+            "ascii" => {
+                self.config.ascii_only = !self.config.ascii_only;
+                theme::set_ascii(self.config.ascii_only);
+                self.save_config();
+                self.toast = Some(Toast::new(if self.config.ascii_only {
+                    "ASCII grids and charts on"
+                } else {
+                    "ASCII grids and charts off"
+                }));
+            }
+            "tutorial" => self.tutorial = Some(0),
+            "daily" => self.start_daily(),
+            "bench" => {
+                self.toast = Some(match self.bench(BENCH_SAMPLES) {
+                    Some(summary) => Toast::new(summary.describe()),
+                    None => Toast::error("Benchmark failed to draw"),
+                });
+            }
+            "debug" => {
+                self.debug = match self.debug {
+                    Some(_) => None,
+                    None => Some(DebugStats::default()),
+                };
+                let state = if self.debug.is_some() { "on" } else { "off" };
+                self.toast = Some(Toast::new(format!("Debug overlay {state}")));
+            }
+            other if other.starts_with("sort ") => {
+                let arg = other[5..].trim();
+                match MenuSort::parse(arg) {
+                    Some(sort) => {
+                        self.menu.set_sort(sort, &self.stats, &self.totals);
+                        self.save_menu_prefs();
+                        self.toast = Some(Toast::new(format!("Menu sorted by {}", sort.label())));
+                    }
+                    None => {
+                        self.toast = Some(Toast::error(format!(
+                            "Unknown sort '{arg}' (best, plays, name, default)"
+                        )));
+                    }
+                }
+            }
+            "zen" => {
+                self.zen = !self.zen;
+                self.toast = Some(Toast::new(if self.zen {
+                    "Zen mode on · : shows the HUD again"
+                } else {
+                    "Zen mode off"
+                }));
+            }
+            "practice" => {
+                self.practice = !self.practice;
+                self.toast = Some(if self.practice {
+                    Toast::warn("Practice mode on · scores will not be saved")
+                } else {
+                    Toast::new("Practice mode off · scores are saved again")
+                });
+            }
+            other if other.starts_with("theme ") => {
+                let arg = other[6..].trim();
+                match Palette::parse(arg) {
+                    Some(palette) => {
+                        theme::set_palette(palette);
+                        self.config.palette = palette;
+                        self.save_config();
+                        self.toast = Some(Toast::new(format!("Palette: {}", palette.label())));
+                    }
+                    None => {
+                        self.toast = Some(Toast::error(format!(
+                            "Unknown theme '{arg}' (default, cb, mono)"
+                        )));
+                    }
+                }
+            }
             "help" => {
                 self.show_help = !self.show_help;
                 let state = if self.show_help { "shown" } else { "hidden" };
                 self.toast = Some(Toast::new(format!("Controls {state}")));
             }
-            other if other.is_empty() => {}
+            "" => {}
             other => {
-                self.toast = Some(Toast::new(format!("Unknown command :{other}")));
+                self.toast = Some(Toast::error(match suggest_command(other) {
+                    Some(known) => format!("Unknown command :{other} · did you mean :{known}?"),
+                    None => format!("Unknown command :{other}"),
+                }));
+            }
+        }
+    }
+
+    /// Restarts the active game from the seed of the current run so the same
+    /// layout comes up again.
+    fn replay(&mut self) {
+        let Some((kind, seed)) = self.active.as_ref().map(|game| (game.kind(), game.seed())) else {
+            self.toast = Some(Toast::warn("Start a game before replaying it"));
+            return;
+        };
+        match seed {
+            Some(seed) => {
+                self.set_active(kind.seeded_state(seed, &self.config));
+                self.toast = Some(Toast::new(format!(
+                    "Replaying {} with seed {seed}",
+                    kind.title()
+                )));
+            }
+            None => {
+                match (kind, self.typing_text.clone()) {
+                    (GameKind::Typing, Some(text)) => self.start_typing_text(&text),
+                    _ => self.set_active(kind.new_state(&self.config)),
+                }
+                self.toast = Some(Toast::warn(format!(
+                    "{} has no seed to replay · restarted",
+                    kind.title()
+                )));
+            }
+        }
+    }
+
+    fn export_json(&mut self, arg: &str) {
+        let path = arg.trim();
+        if path.is_empty() {
+            self.toast = Some(Toast::error("Usage: :export-json <path>"));
+            return;
+        }
+        self.toast = Some(match export::write_json(Path::new(path), &self.stats) {
+            Ok(count) => Toast::new(format!("Exported {count} records to {path}")),
+            Err(err) => Toast::error(format!("Export failed: {err}")),
+        });
+    }
+
+    /// Tags the newest record of the open game, or of the game selected in
+    /// the menu. An empty note clears it.
+    fn annotate_last_record(&mut self, arg: &str) {
+        let kind = match &self.active {
+            Some(game) => Some(game.kind()),
+            None => self.menu.selected_kind(),
+        };
+        let Some(record) = kind
+            .and_then(|kind| self.stats.get_mut(&kind))
+            .and_then(|history| history.last_mut())
+        else {
+            self.toast = Some(Toast::warn("No recorded run to annotate"));
+            return;
+        };
+        let note = arg.trim();
+        record.note = (!note.is_empty()).then(|| note.to_string());
+        self.toast = Some(Toast::new(match &record.note {
+            Some(note) => format!("Noted {}: {note}", record.value),
+            None => format!("Cleared the note on {}", record.value),
+        }));
+        self.dirty = true;
+        self.persist_stats();
+    }
+
+    /// Launches a game by slug from anywhere, replacing the open one.
+    fn play(&mut self, arg: &str) {
+        let arg = arg.trim();
+        let Some(kind) = GameKind::from_slug(arg) else {
+            let slugs: Vec<String> = GameKind::ALL.iter().map(|kind| kind.slug()).collect();
+            self.toast = Some(Toast::error(
+                match closest(arg, slugs.iter().map(String::as_str)) {
+                    Some(slug) => format!("Unknown game '{arg}' · did you mean {slug}?"),
+                    None => format!("Unknown game '{arg}' · e.g. :play aim"),
+                },
+            ));
+            return;
+        };
+        self.show_scores = false;
+        self.menu.clear_filter();
+        self.set_active(kind.new_state(&self.config));
+        self.toast = Some(Toast::new(format!("Playing {}", kind.title())));
+    }
+
+    /// Times `samples` full redraws of the current screen into an
+    /// off-screen buffer the size of the terminal.
+    fn bench(&mut self, samples: usize) -> Option<BenchSummary> {
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, height)).ok()?;
+        let mut timings = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            let start = Instant::now();
+            terminal.draw(|frame| self.render(frame)).ok()?;
+            timings.push(start.elapsed());
+        }
+        BenchSummary::from_samples(&timings)
+    }
+
+    /// Shows the running game's seed so a good run can be shared and
+    /// replayed with `:seed <n>`.
+    fn report_seed(&mut self) {
+        self.toast = Some(match self.active.as_ref().map(|game| game.seed()) {
+            Some(Some(seed)) => Toast::new(format!("Seed {seed}")),
+            Some(None) => Toast::warn("This game has no seed"),
+            None => Toast::warn("Start a game to see its seed"),
+        });
+    }
+
+    fn restart_with_seed(&mut self, arg: &str) {
+        let Some(kind) = self.active.as_ref().map(|game| game.kind()) else {
+            self.toast = Some(Toast::warn("Start a game before seeding it"));
+            return;
+        };
+        match arg.trim().parse::<u64>() {
+            Ok(seed) => {
+                self.set_active(kind.seeded_state(seed, &self.config));
+                self.toast = Some(Toast::new(format!(
+                    "Restarted {} with seed {seed}",
+                    kind.title()
+                )));
+            }
+            Err(_) => {
+                self.toast = Some(Toast::error(format!("Invalid seed: {}", arg.trim())));
             }
         }
     }
 
     fn handle_menu_key(&mut self, key: KeyEvent) {
+        if self.menu.is_filtering() {
+            match key.code {
+                KeyCode::Esc => self.menu.clear_filter(),
+                KeyCode::Enter => self.launch_selected_game(),
+                KeyCode::Backspace => self.menu.pop_filter(),
+                KeyCode::Down => self.menu.next(),
+                KeyCode::Up => self.menu.previous(),
+                KeyCode::Char(ch) if !ch.is_control() => self.menu.push_filter(ch),
+                _ => {}
+            }
+            return;
+        }
         match key.code {
+            KeyCode::Char('/') => self.menu.start_filter(),
             KeyCode::Down | KeyCode::Char('j') => self.menu.next(),
             KeyCode::Up | KeyCode::Char('k') => self.menu.previous(),
             KeyCode::Enter | KeyCode::Char('l') => self.launch_selected_game(),
+            KeyCode::Char('s') => self.show_scores = true,
+            KeyCode::Char('f') => {
+                if let Some((kind, favorite)) = self.menu.toggle_favorite() {
+                    self.save_menu_prefs();
+                    let verb = if favorite { "Pinned" } else { "Unpinned" };
+                    self.toast = Some(Toast::new(format!("{verb} {}", kind.title())));
+                }
+            }
+            KeyCode::Char('c') => {
+                let style = match self.menu.toggle_chart_style() {
+                    ChartStyle::Sparkline => "sparkline",
+                    ChartStyle::Braille => "braille",
+                };
+                self.save_menu_prefs();
+                self.toast = Some(Toast::new(format!("Chart style: {style}")));
+            }
             KeyCode::Char('h') => self.toast = Some(Toast::new("Use enter to launch a game")),
+            // Letters without a binding above jump by title; Shift reaches
+            // the bound ones too, e.g. S for Sequence Memory.
+            KeyCode::Char(ch) if ch.is_alphabetic() => self.menu.type_ahead(ch),
+            _ => {}
+        }
+    }
+
+    fn handle_menu_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.menu.next(),
+            MouseEventKind::ScrollUp => self.menu.previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let again = self.menu.click(mouse.column, mouse.row);
+                if again {
+                    self.launch_selected_game();
+                }
+            }
             _ => {}
         }
     }
 
+    /// Replaces the running game, giving the old one its `end_session`.
+    fn set_active(&mut self, game: Box<dyn Game>) {
+        self.close_active();
+        self.totals.record_launch(game.kind());
+        self.menu
+            .set_sort(self.menu.sort(), &self.stats, &self.totals);
+        self.active = Some(game);
+        self.play_start = Some(Instant::now());
+        self.save_totals();
+    }
+
+    /// Freezes a game whose clock is running; the time until `unpause` is
+    /// handed back to it with `shift_clock`.
+    fn pause(&mut self) {
+        if self.paused_at.is_none() && self.active.as_ref().is_some_and(|game| game.is_animating())
+        {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    fn unpause(&mut self) {
+        if let Some(at) = self.paused_at.take()
+            && let Some(active) = &mut self.active
+        {
+            active.shift_clock(at.elapsed());
+        }
+    }
+
+    /// Leaving a daily game any way other than finishing it abandons the
+    /// challenge; `advance_daily` takes it out first so it survives.
+    fn close_active(&mut self) {
+        self.paused_at = None;
+        if self.daily.take().is_some() {
+            self.toast = Some(Toast::warn("Daily challenge abandoned"));
+        }
+        if let Some(mut game) = self.active.take() {
+            let action = game.end_session();
+            self.handle_game_action(action);
+        }
+        if let Some(start) = self.play_start.take() {
+            self.totals.add_play_time(start.elapsed());
+            self.save_totals();
+        }
+    }
+
+    fn save_totals(&self) {
+        if let Some(path) = &self.totals_path {
+            self.totals.save(path);
+        }
+    }
+
     fn launch_selected_game(&mut self) {
-        let kind = self.menu.selected_kind();
-        self.active = Some(GameState::new(kind));
+        let Some(kind) = self.menu.selected_kind() else {
+            return;
+        };
+        self.menu.clear_filter();
+        self.set_active(kind.new_state(&self.config));
         self.toast = Some(Toast::new(format!("Starting {}", kind.title())));
     }
 
     fn on_tick(&mut self) {
-        if let Some(toast) = &self.toast {
-            if toast.is_expired() {
-                self.toast = None;
-            }
+        if let Some(debug) = &mut self.debug {
+            debug.record_tick(Instant::now());
+            self.dirty = true;
+        }
+        if let Some(toast) = &self.toast
+            && toast.is_expired()
+        {
+            self.toast = None;
+            self.dirty = true;
+        }
+        let clock_second = self.session_start.elapsed().as_secs();
+        if clock_second != self.clock_second {
+            self.clock_second = clock_second;
+            self.dirty = true;
         }
 
+        if self.paused_at.is_some() {
+            return;
+        }
         if let Some(active) = &mut self.active {
+            if active.is_animating() {
+                self.dirty = true;
+            }
             let action = active.handle_tick(Instant::now());
             self.handle_game_action(action);
         }
     }
 
+    fn start_daily(&mut self) {
+        let daily = Daily::today();
+        if let Some(kind) = daily.current() {
+            self.set_active(kind.seeded_state(daily.seed(), &Daily::config(&self.config)));
+            self.toast = Some(Toast::new(format!(
+                "Daily 1/{} · {}",
+                daily.game_count(),
+                kind.title()
+            )));
+            self.daily = Some(daily);
+        }
+    }
+
+    /// Scores the daily game that just recorded a result and launches the
+    /// next one, or reports the total after the last.
+    fn advance_daily(&mut self, kind: GameKind, score: f64) {
+        let Some(mut daily) = self.daily.take() else {
+            return;
+        };
+        if daily.current() != Some(kind) {
+            self.daily = Some(daily);
+            return;
+        }
+        daily.record(kind, score);
+        if let Some(next) = daily.current() {
+            self.set_active(next.seeded_state(daily.seed(), &Daily::config(&self.config)));
+            self.toast = Some(Toast::new(format!(
+                "Daily {}/{} · {}",
+                daily.step(),
+                daily.game_count(),
+                next.title()
+            )));
+            self.daily = Some(daily);
+            return;
+        }
+        let total = daily.total();
+        let path = daily::daily_path();
+        let mut best = path.as_deref().map(DailyBest::load).unwrap_or_default();
+        if total > best.score && !self.practice {
+            best = DailyBest {
+                score: total,
+                day: daily.day(),
+            };
+            if let Some(path) = &path {
+                best.save(path);
+            }
+            self.toast = Some(Toast::new(format!("Daily done · {total}/100 · new best!")));
+        } else {
+            self.toast = Some(Toast::new(format!(
+                "Daily done · {total}/100 · best {}",
+                best.score
+            )));
+        }
+    }
+
     fn handle_game_action(&mut self, action: GameAction) {
+        if let GameAction::Record(record, kind) = &action {
+            self.advance_daily(*kind, record.score);
+        }
         match action {
             GameAction::None => {}
+            GameAction::Record(_, _) if self.practice => {}
             GameAction::Record(record, kind) => {
+                self.dirty = true;
                 let history = self.stats.entry(kind).or_default();
                 history.push(record);
-                if history.len() > HISTORY_LIMIT {
-                    let overflow = history.len() - HISTORY_LIMIT;
-                    history.drain(0..overflow);
-                }
+                trim_history(kind, history, &self.config);
                 self.persist_stats();
+                self.menu
+                    .set_sort(self.menu.sort(), &self.stats, &self.totals);
+                if let Some(feed) = &self.feed {
+                    feed.publish(&self.stats);
+                }
+                if self.config.sound_enabled {
+                    beep();
+                }
+            }
+            GameAction::Bell => beep(),
+            GameAction::Cue => {
+                if self.config.sound_enabled {
+                    beep();
+                }
             }
         }
     }
 
     fn render(&mut self, frame: &mut Frame) {
+        self.render_screen(frame);
+        if theme::palette() == Palette::Mono {
+            let area = frame.size();
+            theme::mono_buffer(frame.buffer_mut(), area);
+        }
+        if theme::dim() {
+            let area = frame.size();
+            theme::dim_buffer(frame.buffer_mut(), area);
+        }
+    }
+
+    fn render_screen(&mut self, frame: &mut Frame) {
+        let size = frame.size();
+        if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+            render_too_small(frame, size);
+            return;
+        }
         let areas = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(3), Constraint::Length(4)])
+            .constraints([Constraint::Min(3), Constraint::Length(self.hud_height())])
             .split(frame.size());
 
-        if let Some(active) = &self.active {
+        if self.show_scores {
+            leaderboard::render(frame, areas[0], &self.stats);
+        } else if let Some(active) = &self.active {
             active.render(frame, areas[0]);
         } else {
-            self.menu.render(frame, areas[0], &self.stats);
+            self.menu.render(frame, areas[0], &self.stats, &self.totals);
+        }
+
+        if areas[1].height == 0 {
+            if let Some(debug) = &mut self.debug {
+                debug.render(frame, areas[0]);
+            }
+            return;
         }
 
         let status_line = if self.show_help {
             Some(if let Some(active) = &self.active {
-                active.status_line()
+                match active.seed() {
+                    Some(seed) => format!("{} · seed {seed}", active.status_line()),
+                    None => active.status_line(),
+                }
             } else {
                 self.menu.status_line()
             })
@@ -231,39 +948,318 @@ impl App {
         };
         let help_line = if self.show_help {
             Some(if self.active.is_some() {
-                "hjkl/arrow keys to move · space/enter to act · :q menu · :qa quit · :help hide"
+                "hjkl/arrow keys to move · space/enter to act · r restarts when over · Esc Esc or :q menu · :qa quit · :help hide"
                     .to_string()
             } else {
-                "j/k to move · enter to play · :q quit · :help to show commands".to_string()
+                "j/k to move · enter to play · s scores · :q quit · :help to show commands"
+                    .to_string()
             })
         } else {
             None
         };
-        let command_text = self.command.as_ref().map(|cmd| format!(":{}", cmd.buffer));
-        let toast_text = self.toast.as_ref().map(|t| t.message.as_str());
+        let toast_text = self.toast.as_ref().map(|t| (t.message.as_str(), t.kind));
         hud::render(
             frame,
             areas[1],
             HudContext {
                 primary: status_line.as_deref().unwrap_or(""),
                 secondary: help_line.as_deref().unwrap_or(""),
-                command: command_text.as_deref(),
+                command: self
+                    .command
+                    .as_ref()
+                    .map(|cmd| (cmd.buffer.as_str(), cmd.caret)),
                 toast: toast_text,
+                wall_time: Some(SystemTime::now()),
+                session_elapsed: Some(self.session_start.elapsed()),
+                practice: self.practice,
+                breadcrumb: self.active.as_ref().map(|game| game.kind().title()),
             },
         );
+        if self.show_about {
+            render_popup(frame, areas[0], "About", about_lines());
+        }
+        if self.show_probe {
+            render_popup(frame, areas[0], "Terminal probe", probe_lines());
+        }
+        if self.paused_at.is_some() {
+            render_popup(
+                frame,
+                areas[0],
+                "Paused",
+                vec![
+                    Line::from("The terminal lost focus · the clock is stopped"),
+                    Line::styled(
+                        "focus it again or press any key",
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ],
+            );
+        }
+        if let Some(step) = self.tutorial {
+            render_popup(frame, areas[0], "Tutorial", tutorial_lines(step));
+        }
+        if let Some(debug) = &mut self.debug {
+            debug.render(frame, areas[0]);
+        }
+    }
+}
+
+fn about_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::styled(
+            format!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Line::from(env!("CARGO_PKG_DESCRIPTION")),
+        Line::from(format!(
+            "by {}",
+            env!("CARGO_PKG_AUTHORS").replace(':', ", ")
+        )),
+        Line::from(""),
+        Line::styled("any key to close", Style::default().fg(Color::DarkGray)),
+    ]
+}
+
+/// A test card of the colors and glyphs the games draw with, so a terminal
+/// that lacks some of them can be spotted and `:ascii` or `:theme` picked.
+fn probe_lines() -> Vec<Line<'static>> {
+    let swatches = |label: &'static str, colors: Vec<Color>| {
+        let mut spans = vec![Span::raw(format!("{label:<9}"))];
+        spans.extend(
+            colors
+                .into_iter()
+                .map(|color| Span::styled("  ", Style::default().bg(color))),
+        );
+        Line::from(spans)
+    };
+    let named = vec![
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    let indexed = (0..8).map(|step| Color::Indexed(196 + step * 6)).collect();
+    let rgb = (0..8)
+        .map(|step| Color::Rgb(255 - step * 32, step * 32, 128))
+        .collect();
+    let sparks: String = menu::SPARKS.iter().collect();
+    vec![
+        swatches("16 color", named),
+        swatches("256", indexed),
+        swatches("truecolor", rgb),
+        Line::from(format!("{:<9}{sparks}", "bars")),
+        Line::from(format!("{:<9}⠁⠃⠇⡇⣇⣧⣷⣿", "braille")),
+        Line::from(format!("{:<9}■ · ● ⌖ ✚ ›", "glyphs")),
+        Line::from(""),
+        Line::from("Broken rows? :ascii for plain grids and charts, :theme cb for colors"),
+        Line::styled("any key to close", Style::default().fg(Color::DarkGray)),
+    ]
+}
+
+/// First-run walkthrough, one step per keypress.
+const TUTORIAL: &[(&str, &[&str])] = &[
+    (
+        "Welcome",
+        &["Quick brain games, played with vim keys in the terminal."],
+    ),
+    (
+        "Moving",
+        &[
+            "j/k or ↑/↓ pick a game in the menu.",
+            "In games h/j/k/l move the cursor; counts like 5j work.",
+        ],
+    ),
+    (
+        "Playing",
+        &[
+            "Enter launches the selected game.",
+            "Space or Enter acts in a game; Esc twice goes back.",
+        ],
+    ),
+    (
+        "Commands",
+        &[
+            "Press : for the command line; Ctrl-R there brings back one cleared with Esc.",
+            ":help shows the controls, :scores the leaderboard, :q leaves.",
+        ],
+    ),
+    ("That's it", &[":tutorial shows this again. Have fun!"]),
+];
+
+/// The tutorial opens by itself on a first launch, which is when there is
+/// no scores file yet, unless it was already finished or skipped.
+fn opening_tutorial(stats_path: &Path, config: &Config) -> Option<usize> {
+    (!stats_path.exists() && !config.tutorial_seen).then_some(0)
+}
+
+fn tutorial_lines(step: usize) -> Vec<Line<'static>> {
+    let (title, body) = TUTORIAL[step.min(TUTORIAL.len() - 1)];
+    let hint = if step + 1 >= TUTORIAL.len() {
+        "any key to start".to_string()
+    } else {
+        format!(
+            "{}/{} · any key for next · Esc to skip",
+            step + 1,
+            TUTORIAL.len()
+        )
+    };
+    let mut lines = vec![Line::styled(
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    lines.extend(body.iter().map(|line| Line::from(*line)));
+    lines.push(Line::from(""));
+    lines.push(Line::styled(hint, Style::default().fg(Color::DarkGray)));
+    lines
+}
+
+/// A centered box over whatever is on screen, sized to its lines.
+fn render_popup(frame: &mut Frame, area: Rect, title: &str, lines: Vec<Line<'static>>) {
+    let width = lines
+        .iter()
+        .map(|line| line.width() as u16 + 4)
+        .max()
+        .unwrap_or(0)
+        .min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Gray)),
+        ),
+        popup,
+    );
+}
+
+fn render_too_small(frame: &mut Frame, area: Rect) {
+    let text = vec![
+        Line::from("Terminal too small"),
+        Line::from(format!(
+            "{}×{} · need {MIN_WIDTH}×{MIN_HEIGHT}",
+            area.width, area.height
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(Color::Yellow)),
+        area,
+    );
+}
+
+/// Every command `execute_command` knows, by its first word, for typo
+/// suggestions.
+const COMMANDS: &[&str] = &[
+    "quit",
+    "quitall",
+    "menu",
+    "restart",
+    "replay",
+    "resume",
+    "export-json",
+    "seed",
+    "play",
+    "note",
+    "scores",
+    "about",
+    "version",
+    "tutorial",
+    "daily",
+    "debug",
+    "bench",
+    "sort",
+    "practice",
+    "zen",
+    "probe",
+    "ascii",
+    "dim",
+    "theme",
+    "help",
+];
+
+/// The closest known command to a mistyped one, if it is within two edits
+/// and not just a rewrite of a very short input. An exact match means the
+/// command was missing its argument, which a suggestion would not fix.
+fn suggest_command(input: &str) -> Option<&'static str> {
+    closest(input.split_whitespace().next()?, COMMANDS.iter().copied())
+}
+
+/// The entry of `known` within two edits of `word`, the nearest first.
+fn closest<'a>(word: &str, known: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    known
+        .into_iter()
+        .map(|known| (edit_distance(word, known), known))
+        .filter(|(distance, _)| (1..=2).contains(distance) && *distance < word.chars().count())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
     }
+    row[b.len()]
+}
+
+/// Rings the terminal bell. Only called from the app loop between draws, so
+/// the byte never lands in the middle of a frame.
+fn beep() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
 }
 
 #[derive(Debug, Clone)]
 struct Toast {
     message: String,
+    kind: ToastKind,
     expires_at: Instant,
 }
 
 impl Toast {
     fn new(message: impl Into<String>) -> Self {
+        Self::with_kind(ToastKind::Info, message)
+    }
+
+    fn warn(message: impl Into<String>) -> Self {
+        Self::with_kind(ToastKind::Warn, message)
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self::with_kind(ToastKind::Error, message)
+    }
+
+    fn with_kind(kind: ToastKind, message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            kind,
             expires_at: Instant::now() + Duration::from_secs(3),
         }
     }
@@ -273,74 +1269,923 @@ impl Toast {
     }
 }
 
-fn load_persisted_stats() -> (HashMap<GameKind, Vec<StatRecord>>, Option<PathBuf>) {
-    let path = stats_file_path();
-    if let Some(path_ref) = &path {
-        if let Ok(bytes) = fs::read(path_ref) {
-            if let Ok(map) = serde_json::from_slice::<HashMap<GameKind, Vec<StatRecord>>>(&bytes) {
-                return (map, path);
+/// Score history split into games this build knows and keys it does not
+/// recognise (from a newer version or a hand edit). Unknown entries are kept
+/// so saving does not drop them.
+#[derive(Default)]
+struct PersistedStats {
+    stats: HashMap<GameKind, Vec<StatRecord>>,
+    unknown: HashMap<String, Vec<StatRecord>>,
+}
+
+/// Also returns whether the scores fell back to the working directory.
+fn load_persisted_stats() -> (PersistedStats, PathBuf, bool) {
+    let (path, local) = stats_file_path();
+    let persisted = fs::read(&path)
+        .map(|bytes| split_stats(&bytes))
+        .unwrap_or_default();
+    (persisted, path, local)
+}
+
+fn split_stats(bytes: &[u8]) -> PersistedStats {
+    let mut persisted = PersistedStats::default();
+    for (key, history) in parse_stats(bytes) {
+        match GameKind::from_persist_key(&key) {
+            Some(kind) => {
+                persisted.stats.insert(kind, history);
             }
-            if let Ok(legacy) =
-                serde_json::from_slice::<HashMap<GameKind, LegacyStatRecord>>(&bytes)
-            {
-                let converted = legacy
-                    .into_iter()
-                    .map(|(kind, record)| {
-                        let score = parse_legacy_score(kind, &record.value);
-                        let converted = StatRecord {
-                            label: record.label,
-                            value: record.value,
-                            score,
-                            recorded_at: 0,
-                        };
-                        (kind, vec![converted])
-                    })
-                    .collect();
-                return (converted, path);
+            None => {
+                persisted.unknown.insert(key, history);
             }
         }
     }
-    (HashMap::new(), path)
+    persisted
 }
 
-fn stats_file_path() -> Option<PathBuf> {
+/// Shrinks `history` to the configured limit, oldest first. With
+/// `keep-best` the all-time best under each label is skipped over so it is
+/// never dropped.
+fn trim_history(kind: GameKind, history: &mut Vec<StatRecord>, config: &Config) {
+    let limit = config.history_limit.max(1);
+    if history.len() <= limit {
+        return;
+    }
+    let overflow = history.len() - limit;
+    let keep: Vec<usize> = match config.history_trim {
+        HistoryTrim::Oldest => Vec::new(),
+        HistoryTrim::KeepBest => menu::label_bests(kind, history)
+            .into_iter()
+            .filter_map(|best| history.iter().position(|record| std::ptr::eq(record, best)))
+            .collect(),
+    };
+    let mut idx = 0;
+    let mut dropped = 0;
+    history.retain(|_| {
+        let retain = dropped == overflow || keep.contains(&idx);
+        if !retain {
+            dropped += 1;
+        }
+        idx += 1;
+        retain
+    });
+}
+
+fn session_file_path() -> Option<PathBuf> {
     let mut dir = config_dir()?;
     dir.push("bored");
-    dir.push("scores.json");
+    dir.push("session.json");
     Some(dir)
 }
 
+/// Loads the run saved by the previous session and removes the file, so a
+/// snapshot is offered once and never resumed twice.
+fn take_saved_session() -> Option<Snapshot> {
+    let path = session_file_path()?;
+    let bytes = fs::read(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// `ARCADE_STATS_PATH` wins over the config dir. With neither, scores go
+/// to `./scores.json` rather than not being kept at all, and the flag is set
+/// so the user can be told.
+fn stats_file_path() -> (PathBuf, bool) {
+    if let Some(path) = env::var_os(STATS_PATH_ENV).filter(|path| !path.is_empty()) {
+        return (PathBuf::from(path), false);
+    }
+    match config_dir() {
+        Some(mut dir) => {
+            dir.push("bored");
+            dir.push("scores.json");
+            (dir, false)
+        }
+        None => (PathBuf::from("scores.json"), true),
+    }
+}
+
 impl App {
+    /// Writes the current menu sort and favorites back to `config.toml` so
+    /// they survive a restart.
+    fn save_menu_prefs(&mut self) {
+        self.config.menu.sort = self.menu.sort();
+        self.config.menu.chart = self.menu.chart_style();
+        self.config.menu.favorites = self
+            .menu
+            .favorites()
+            .into_iter()
+            .map(|kind| kind.persist_key().to_string())
+            .collect();
+        self.save_config();
+    }
+
+    fn save_config(&self) {
+        if let Some(path) = &self.config_path {
+            self.config.save(path);
+        }
+    }
+
+    /// Saves the active game's snapshot on exit, if it offers one. A saved
+    /// run that was never resumed is carried over to the next launch.
+    fn save_session(&self) {
+        let Some(snapshot) = self
+            .active
+            .as_ref()
+            .and_then(|game| game.snapshot())
+            .or_else(|| self.resume.clone())
+        else {
+            return;
+        };
+        let Some(path) = session_file_path() else {
+            return;
+        };
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec(&snapshot)
+            && let Err(err) = fs::write(&path, json)
+        {
+            errlog::append(&format!("saving {}: {err}", path.display()));
+        }
+    }
+
     fn persist_stats(&self) {
         if let Some(path) = &self.stats_path {
-            if let Some(parent) = path.parent() {
-                if fs::create_dir_all(parent).is_err() {
-                    return;
-                }
+            if let Some(parent) = path.parent()
+                && fs::create_dir_all(parent).is_err()
+            {
+                return;
             }
-            if let Ok(json) = serde_json::to_vec_pretty(&self.stats) {
-                let _ = fs::write(path, json);
+            let mut games: HashMap<&str, &Vec<StatRecord>> = self
+                .unknown_stats
+                .iter()
+                .map(|(key, history)| (key.as_str(), history))
+                .collect();
+            games.extend(
+                self.stats
+                    .iter()
+                    .map(|(kind, history)| (kind.persist_key(), history)),
+            );
+            let file = StatsFile {
+                version: STATS_VERSION,
+                games,
+            };
+            if let Ok(json) = serde_json::to_vec_pretty(&file)
+                && let Err(err) = fs::write(path, json)
+            {
+                errlog::append(&format!("saving {}: {err}", path.display()));
             }
         }
     }
 }
 
+/// Version of the `scores.json` layout written by this build. Version 0
+/// was the bare map of game key to history, before that a single record
+/// per game, neither of which always carried a usable `score`.
+const STATS_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct StatsFile<K: Eq + std::hash::Hash, H> {
+    version: u32,
+    games: HashMap<K, H>,
+}
+
+/// A record as older files stored it, with everything but the label and
+/// value optional.
 #[derive(Deserialize)]
 struct LegacyStatRecord {
     label: String,
     value: String,
+    #[serde(default)]
+    score: Option<f64>,
+    #[serde(default)]
+    recorded_at: u64,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+impl LegacyStatRecord {
+    /// A missing or zero score is re-derived from the value text.
+    fn migrate(self) -> StatRecord {
+        let score = self
+            .score
+            .filter(|score| score.is_finite() && *score != 0.0)
+            .unwrap_or_else(|| parse_legacy_score(&self.value));
+        StatRecord {
+            label: self.label,
+            value: self.value,
+            score,
+            recorded_at: self.recorded_at,
+            note: self.note,
+        }
+    }
 }
 
-fn parse_legacy_score(_kind: GameKind, value: &str) -> f64 {
-    value
-        .chars()
-        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
-        .collect::<String>()
-        .parse::<f64>()
-        .unwrap_or(0.0)
+/// Reads any version of `scores.json`, migrating older layouts. Anything
+/// unreadable gives an empty history.
+fn parse_stats(bytes: &[u8]) -> HashMap<String, Vec<StatRecord>> {
+    if let Ok(file) = serde_json::from_slice::<StatsFile<String, Vec<StatRecord>>>(bytes) {
+        return file.games;
+    }
+    let legacy = serde_json::from_slice::<HashMap<String, Vec<LegacyStatRecord>>>(bytes)
+        .ok()
+        .or_else(|| {
+            let single = serde_json::from_slice::<HashMap<String, LegacyStatRecord>>(bytes).ok()?;
+            Some(
+                single
+                    .into_iter()
+                    .map(|(key, record)| (key, vec![record]))
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+    legacy
+        .into_iter()
+        .map(|(key, history)| {
+            let history = history.into_iter().map(LegacyStatRecord::migrate).collect();
+            (key, history)
+        })
+        .collect()
 }
 
+/// The number a record's value starts with, whatever unit follows it:
+/// "1840 ms" is 1840, "92%" is 92 and "7 · 3.4s" is 7. Every game writes
+/// its score first, so trailing detail is ignored rather than run together.
+fn parse_legacy_score(value: &str) -> f64 {
+    let start = value
+        .find(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+        .unwrap_or(value.len());
+    let number = &value[start..];
+    let end = number
+        .char_indices()
+        .find(|&(idx, c)| !(c.is_ascii_digit() || c == '.' || (idx == 0 && c == '-')))
+        .map_or(number.len(), |(idx, _)| idx);
+    number[..end].parse::<f64>().unwrap_or(0.0)
+}
+
+/// The `:` command line. `caret` counts chars, not bytes, and sits between
+/// characters; `buffer.chars().count()` means the end.
 #[derive(Default)]
 struct CommandPalette {
     buffer: String,
+    caret: usize,
+}
+
+impl CommandPalette {
+    fn byte_offset(&self, caret: usize) -> usize {
+        self.buffer
+            .char_indices()
+            .nth(caret)
+            .map(|(offset, _)| offset)
+            .unwrap_or(self.buffer.len())
+    }
+
+    fn insert(&mut self, ch: char) {
+        let offset = self.byte_offset(self.caret);
+        self.buffer.insert(offset, ch);
+        self.caret += 1;
+    }
+
+    /// Removes the char before the caret.
+    fn backspace(&mut self) {
+        if self.caret == 0 {
+            return;
+        }
+        self.caret -= 1;
+        let offset = self.byte_offset(self.caret);
+        self.buffer.remove(offset);
+    }
+
+    /// Removes the char under the caret.
+    fn delete(&mut self) {
+        if self.caret < self.buffer.chars().count() {
+            let offset = self.byte_offset(self.caret);
+            self.buffer.remove(offset);
+        }
+    }
+
+    fn left(&mut self) {
+        self.caret = self.caret.saturating_sub(1);
+    }
+
+    fn right(&mut self) {
+        self.caret = (self.caret + 1).min(self.buffer.chars().count());
+    }
+}
+
+#[cfg(test)]
+impl App {
+    /// An app on default settings that never reads or writes the config
+    /// dir, so tests leave the player's files alone.
+    fn offline() -> Self {
+        Self {
+            menu: MenuState::default(),
+            active: None,
+            stats: HashMap::new(),
+            unknown_stats: HashMap::new(),
+            should_quit: false,
+            toast: None,
+            command: None,
+            last_discarded: None,
+            stats_path: None,
+            config: Config::default(),
+            config_path: None,
+            show_help: false,
+            show_scores: false,
+            show_about: false,
+            show_probe: false,
+            zen: false,
+            tutorial: None,
+            session_start: Instant::now(),
+            debug: None,
+            dirty: true,
+            clock_second: 0,
+            practice: false,
+            resume: None,
+            last_esc: None,
+            totals: Totals::default(),
+            totals_path: None,
+            play_start: None,
+            daily: None,
+            feed: None,
+            paused_at: None,
+            typing_text: None,
+        }
+    }
+
+    /// Drives the app as `run` would, with a tick after every event.
+    fn feed(&mut self, events: &[Event]) {
+        for event in events {
+            self.handle_event(event.clone());
+            self.on_tick();
+        }
+    }
+
+    /// Draws one frame and returns the screen as text, one line per row.
+    fn render_text(&mut self, width: u16, height: u16) -> String {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).expect("test backend");
+        terminal.draw(|frame| self.render(frame)).expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(text: &str) -> Vec<Event> {
+        text.chars()
+            .map(|ch| Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE)))
+            .collect()
+    }
+
+    fn enter() -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn configured_split_sets_the_menu_panes() {
+        let mut app = App::offline();
+        app.config.menu.list_percent = 30;
+        app.menu.set_list_percent(app.config.menu.list_percent);
+        assert_eq!(
+            app.menu.pane_constraints(),
+            [Constraint::Percentage(30), Constraint::Percentage(70)]
+        );
+
+        app.menu.set_list_percent(95);
+        assert_eq!(
+            app.menu.pane_constraints(),
+            [Constraint::Percentage(80), Constraint::Percentage(20)]
+        );
+    }
+
+    #[test]
+    fn ctrl_r_restores_a_command_discarded_with_esc() {
+        let mut app = App::offline();
+        let esc = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        let ctrl_r = || Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        app.feed(&keys(":export-json /very/long/path"));
+        app.feed(&[esc]);
+        assert!(app.command.is_none());
+
+        app.feed(&keys(":"));
+        app.feed(&[ctrl_r()]);
+        let command = app.command.as_ref().expect("the palette is open");
+        assert_eq!(command.buffer, "export-json /very/long/path");
+        assert_eq!(command.caret, command.buffer.chars().count());
+
+        // Only the latest discard is kept, and restoring uses it up.
+        app.feed(&[ctrl_r()]);
+        assert_eq!(
+            app.command.as_ref().unwrap().buffer,
+            "export-json /very/long/path"
+        );
+        assert!(app.last_discarded.is_none());
+    }
+
+    #[test]
+    fn unversioned_scores_are_migrated() {
+        let stats = parse_stats(
+            r#"{"Reaction": [{"label": "Best", "value": "1840 ms", "recorded_at": 5}],
+                "ChimpTest": [{"label": "Level", "value": "7 · 3.4s", "score": 0.0, "recorded_at": 6}]}"#
+                .as_bytes(),
+        );
+        assert_eq!(stats["Reaction"][0].score, 1840.0);
+        assert_eq!(stats["Reaction"][0].recorded_at, 5);
+        assert_eq!(stats["ChimpTest"][0].score, 7.0);
+
+        let legacy = parse_stats(br#"{"Reaction": {"label": "Best", "value": "215 ms"}}"#);
+        assert_eq!(legacy["Reaction"][0].score, 215.0);
+    }
+
+    #[test]
+    fn bench_times_every_sample() {
+        let mut app = App::offline();
+        let summary = app.bench(5).expect("a summary");
+        assert_eq!(summary.samples, 5);
+        assert!(summary.min <= summary.mean && summary.mean <= summary.max);
+        assert!(app.bench(0).is_none());
+    }
+
+    #[test]
+    fn restart_command_rebuilds_the_active_game() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        app.feed(&keys("stil"));
+        let seed = app.active.as_ref().and_then(|game| game.seed());
+
+        app.feed(&keys(":restart"));
+        assert!(app.render_text(80, 24).contains(":restart"));
+        app.feed(&[enter()]);
+
+        let game = app.active.as_ref().expect("a game is still open");
+        assert_eq!(game.kind(), GameKind::Typing);
+        assert_ne!(game.seed(), seed);
+        assert_eq!(app.totals.launches, 2);
+        assert!(app.command.is_none());
+        assert!(app.render_text(80, 24).contains("Restarted Typing"));
+    }
+
+    #[test]
+    fn focus_loss_pauses_a_running_game() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        app.feed(&[Event::FocusLost]);
+        assert!(
+            app.paused_at.is_none(),
+            "an idle sprint has no clock to stop"
+        );
+
+        app.feed(&keys("s"));
+        app.feed(&[Event::FocusLost]);
+        assert!(app.paused_at.is_some());
+        assert!(app.render_text(80, 24).contains("Paused"));
+
+        app.feed(&[Event::FocusGained]);
+        assert!(app.paused_at.is_none());
+    }
+
+    #[test]
+    fn play_command_switches_games() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        app.feed(&keys(":play aim"));
+        app.feed(&[enter()]);
+        assert_eq!(
+            app.active.as_ref().map(|game| game.kind()),
+            Some(GameKind::AimTrainer)
+        );
+
+        app.feed(&keys(":play raction"));
+        app.feed(&[enter()]);
+        assert_eq!(
+            app.active.as_ref().map(|game| game.kind()),
+            Some(GameKind::AimTrainer)
+        );
+        assert!(app.render_text(80, 24).contains("did you mean reaction?"));
+    }
+
+    #[test]
+    fn enter_launches_the_first_filtered_game() {
+        let mut app = App::offline();
+        app.feed(&keys("/MeM"));
+        assert_eq!(app.menu.selected_kind(), Some(GameKind::Sequence));
+        app.feed(&[enter()]);
+        assert_eq!(
+            app.active.as_ref().map(|game| game.kind()),
+            Some(GameKind::Sequence)
+        );
+        assert!(!app.menu.is_filtering());
+    }
+
+    #[test]
+    fn unknown_command_toasts_an_error() {
+        let mut app = App::offline();
+        app.feed(&keys(":frobnicate"));
+        app.feed(&[enter()]);
+        let toast = app.toast.as_ref().expect("a toast");
+        assert_eq!(toast.kind, ToastKind::Error);
+        assert!(toast.message.starts_with("Unknown command"));
+    }
+
+    #[test]
+    fn handled_events_count_toward_events_per_second() {
+        let mut app = App::offline();
+        app.feed(&keys(":debug"));
+        app.feed(&[enter()]);
+        let debug = app.debug.as_mut().expect("the overlay is on");
+        let before = debug.events_per_second(Instant::now());
+        app.feed(&keys("jjj"));
+        let debug = app.debug.as_mut().unwrap();
+        assert_eq!(debug.events_per_second(Instant::now()), before + 3);
+    }
+
+    #[test]
+    fn idle_menu_tick_leaves_the_screen_clean() {
+        let mut app = App::offline();
+        app.dirty = false;
+        app.on_tick();
+        assert!(!app.dirty);
+    }
+
+    #[test]
+    fn resize_marks_the_screen_dirty() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        app.dirty = false;
+        app.handle_event(Event::Resize(120, 40));
+        assert!(app.dirty);
+    }
+
+    #[test]
+    fn dim_mode_keeps_the_menu_selection_at_full_intensity() {
+        let mut app = App::offline();
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(80, 20)).expect("test backend");
+        theme::set_dim(true);
+        terminal.draw(|frame| app.render(frame)).expect("draw");
+        theme::set_dim(false);
+        let buffer = terminal.backend().buffer();
+        let selected = buffer.get(1, 1);
+        assert!(selected.modifier.contains(Modifier::REVERSED));
+        assert!(!selected.modifier.contains(Modifier::DIM));
+        assert!(buffer.get(1, 2).modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn unknown_game_keys_do_not_drop_the_known_ones() {
+        let persisted = split_stats(
+            br#"{"version": 1, "games": {
+                "Reaction": [{"label": "Reaction", "value": "210 ms", "score": 210.0, "recorded_at": 1}],
+                "Snake": [{"label": "Length", "value": "40", "score": 40.0, "recorded_at": 2}]}}"#,
+        );
+        assert_eq!(persisted.stats[&GameKind::Reaction][0].score, 210.0);
+        assert_eq!(persisted.stats.len(), 1);
+        assert_eq!(persisted.unknown["Snake"][0].score, 40.0);
+    }
+
+    #[test]
+    fn practice_runs_leave_the_stats_alone() {
+        let mut app = App::offline();
+        app.feed(&keys(":practice"));
+        app.feed(&[enter()]);
+        assert!(app.practice);
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "200 ms", 200.0),
+            GameKind::Reaction,
+        ));
+        assert!(app.stats.is_empty());
+
+        app.practice = false;
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "200 ms", 200.0),
+            GameKind::Reaction,
+        ));
+        assert_eq!(app.stats[&GameKind::Reaction].len(), 1);
+    }
+
+    #[test]
+    fn keep_best_trim_never_drops_the_best() {
+        let config = Config {
+            history_limit: 10,
+            history_trim: HistoryTrim::KeepBest,
+            ..Config::default()
+        };
+        let mut history = vec![StatRecord::new("Reaction", "150 ms", 150.0)];
+        for ms in 0..100 {
+            history.push(StatRecord::new("Reaction", "slow", 300.0 + f64::from(ms)));
+            trim_history(GameKind::Reaction, &mut history, &config);
+        }
+        assert_eq!(history.len(), 10);
+        assert_eq!(history[0].score, 150.0);
+        assert_eq!(history[9].score, 399.0);
+
+        let oldest = Config {
+            history_trim: HistoryTrim::Oldest,
+            ..config
+        };
+        history.push(StatRecord::new("Reaction", "slow", 400.0));
+        trim_history(GameKind::Reaction, &mut history, &oldest);
+        assert!(history.iter().all(|record| record.score > 150.0));
+    }
+
+    #[test]
+    fn a_running_beat_ticks_faster_than_idle_reaction() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Reaction.new_state(&app.config));
+        let reaction = app.tick_rate();
+        assert!(reaction > app.config.tick_rate());
+
+        app.set_active(GameKind::Rhythm.new_state(&app.config));
+        app.feed(&[enter()]);
+        assert!(app.tick_rate() < app.config.tick_rate());
+        assert!(app.tick_rate() < reaction);
+    }
+
+    #[test]
+    fn palette_caret_moves_and_edits_mid_buffer() {
+        let mut app = App::offline();
+        let press = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        app.feed(&keys(":srot name"));
+        app.feed(&vec![press(KeyCode::Left); 6]);
+        app.feed(&[press(KeyCode::Backspace), press(KeyCode::Backspace)]);
+        app.feed(&keys("or"));
+        let command = app.command.as_ref().unwrap();
+        assert_eq!(command.buffer, "sort name");
+        assert_eq!(command.caret, 3);
+
+        app.feed(&[press(KeyCode::Right), press(KeyCode::Delete)]);
+        app.feed(&vec![press(KeyCode::Right); 20]);
+        let command = app.command.as_ref().unwrap();
+        assert_eq!(command.buffer, "sortname");
+        assert_eq!(command.caret, 8);
+        assert!(app.render_text(40, 24).contains(":sortname"));
+    }
+
+    #[test]
+    fn double_esc_returns_to_the_menu() {
+        let mut app = App::offline();
+        let esc = || Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        app.set_active(GameKind::AimTrainer.new_state(&app.config));
+        app.feed(&[esc()]);
+        assert!(app.active.is_some(), "a single Esc stays with the game");
+        app.feed(&[esc()]);
+        assert!(app.active.is_none());
+    }
+
+    #[test]
+    fn tiny_terminals_render_without_panicking() {
+        let mut app = App::offline();
+        assert!(app.render_text(80, 5).contains("Terminal too small"));
+        for kind in GameKind::ALL {
+            app.set_active(kind.new_state(&app.config));
+            assert!(app.render_text(80, 5).contains("Terminal too small"));
+            app.render_text(MIN_WIDTH, MIN_HEIGHT);
+            app.render_text(1, 1);
+
+            // The games themselves cope with a short area too.
+            let mut terminal =
+                Terminal::new(ratatui::backend::TestBackend::new(80, 5)).expect("test backend");
+            let game = kind.new_state(&app.config);
+            terminal
+                .draw(|frame| game.render(frame, frame.size()))
+                .expect("draw");
+        }
+    }
+
+    #[test]
+    fn about_shows_the_compiled_version() {
+        let text: String = about_lines()
+            .iter()
+            .flat_map(|line| line.spans.iter().map(|span| span.content.to_string()))
+            .collect();
+        assert!(text.contains(&format!("v{}", env!("CARGO_PKG_VERSION"))));
+
+        let mut app = App::offline();
+        app.feed(&keys(":about"));
+        app.feed(&[enter()]);
+        assert!(app.render_text(80, 24).contains(env!("CARGO_PKG_VERSION")));
+        app.feed(&keys("x"));
+        assert!(!app.show_about);
+    }
+
+    #[test]
+    fn launching_and_leaving_a_game_adds_to_the_totals() {
+        let mut app = App::offline();
+        let esc = || Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        app.feed(&[enter()]);
+        assert_eq!(app.totals.launches, 1);
+        std::thread::sleep(Duration::from_millis(5));
+        app.feed(&[esc(), esc()]);
+        assert!(app.active.is_none());
+        assert!(app.totals.play_ms >= 5);
+        assert!(app.play_start.is_none());
+    }
+
+    #[test]
+    fn close_typos_get_a_suggestion() {
+        assert_eq!(suggest_command("qut"), Some("quit"));
+        assert_eq!(suggest_command("resatrt"), Some("restart"));
+        assert_eq!(suggest_command("xyzzyplugh"), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn zen_gives_the_game_the_full_height() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        let last_row = |app: &mut App| app.render_text(40, 24).lines().last().unwrap().to_string();
+        assert!(!last_row(&mut app).starts_with('└'));
+
+        app.feed(&keys(":zen"));
+        app.feed(&[enter()]);
+        assert_eq!(app.hud_height(), 0);
+        assert!(last_row(&mut app).starts_with('└'));
+
+        app.feed(&keys(":"));
+        assert_eq!(app.hud_height(), 4);
+    }
+
+    #[test]
+    fn tutorial_opens_on_a_first_launch_only() {
+        let missing = std::env::temp_dir().join(format!("arcade-no-scores-{}", std::process::id()));
+        let config = Config::default();
+        assert_eq!(opening_tutorial(&missing, &config), Some(0));
+
+        let seen = Config {
+            tutorial_seen: true,
+            ..Config::default()
+        };
+        assert_eq!(opening_tutorial(&missing, &seen), None);
+        assert_eq!(
+            opening_tutorial(Path::new(env!("CARGO_MANIFEST_DIR")), &config),
+            None
+        );
+    }
+
+    #[test]
+    fn daily_moves_on_once_the_first_game_records() {
+        let mut app = App::offline();
+        let daily = Daily::for_day(20_000);
+        let first = daily.current().unwrap();
+        app.set_active(first.seeded_state(daily.seed(), &Daily::config(&app.config)));
+        app.daily = Some(daily);
+
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "250 ms", 250.0),
+            first,
+        ));
+        let daily = app.daily.as_ref().expect("the challenge goes on");
+        assert_eq!(daily.step(), 2);
+        let second = daily.current().unwrap();
+        assert_ne!(second, first);
+        assert_eq!(app.active.as_ref().map(|game| game.kind()), Some(second));
+    }
+
+    #[test]
+    fn note_is_saved_with_the_latest_record() {
+        let path = std::env::temp_dir().join(format!("arcade-note-{}.json", std::process::id()));
+        let mut app = App::offline();
+        app.stats_path = Some(path.clone());
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "240 ms", 240.0),
+            GameKind::Reaction,
+        ));
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "220 ms", 220.0),
+            GameKind::Reaction,
+        ));
+        app.feed(&keys(":note new keyboard "));
+        app.feed(&[enter()]);
+
+        let saved = split_stats(&fs::read(&path).unwrap());
+        fs::remove_file(&path).ok();
+        let history = &saved.stats[&GameKind::Reaction];
+        assert_eq!(history[0].note, None);
+        assert_eq!(history[1].note.as_deref(), Some("new keyboard"));
+    }
+
+    #[test]
+    fn stats_path_env_var_overrides_the_config_dir() {
+        let path = std::env::temp_dir().join(format!("arcade-env-{}.json", std::process::id()));
+        // SAFETY: no other test reads or writes this variable.
+        unsafe { env::set_var(STATS_PATH_ENV, &path) };
+        let mut app = App::offline();
+        app.stats_path = Some(stats_file_path().0);
+        app.handle_game_action(GameAction::Record(
+            StatRecord::new("Reaction", "230 ms", 230.0),
+            GameKind::Reaction,
+        ));
+        let (persisted, loaded_from, local) = load_persisted_stats();
+        unsafe { env::remove_var(STATS_PATH_ENV) };
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded_from, path);
+        assert!(!local);
+        assert_eq!(persisted.stats[&GameKind::Reaction][0].score, 230.0);
+    }
+
+    #[test]
+    fn r_restarts_a_finished_typing_round() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Typing.new_state(&app.config));
+        app.feed(&keys("the"));
+        app.feed(&[enter()]);
+        assert!(app.render_text(100, 24).contains("r to restart"));
+
+        app.feed(&keys("r"));
+        let status = app.active.as_ref().unwrap().status_line();
+        assert!(
+            app.render_text(100, 24).contains("start typing to begin"),
+            "{status}"
+        );
+        assert_eq!(app.totals.launches, 1);
+    }
+
+    fn aim_target(app: &mut App) -> Option<(usize, usize)> {
+        app.render_text(80, 40)
+            .lines()
+            .enumerate()
+            .find_map(|(y, line)| {
+                line.chars()
+                    .position(|ch| ch == '●' || ch == 'O')
+                    .map(|x| (x, y))
+            })
+    }
+
+    #[test]
+    fn replay_reruns_the_same_seed() {
+        let mut app = App::offline();
+        app.set_active(GameKind::AimTrainer.seeded_state(123, &app.config));
+        let first_target = aim_target(&mut app);
+        assert!(first_target.is_some());
+        app.feed(&keys("lljj"));
+        app.feed(&keys(":replay"));
+        app.feed(&[enter()]);
+        assert_eq!(app.active.as_ref().unwrap().seed(), Some(123));
+        assert_eq!(
+            app.toast.as_ref().unwrap().message,
+            "Replaying Aim Trainer with seed 123"
+        );
+        assert_eq!(aim_target(&mut app), first_target);
+    }
+
+    #[test]
+    fn replay_without_a_seed_restarts_with_a_warning() {
+        let mut app = App::offline();
+        app.set_active(GameKind::Rhythm.new_state(&app.config));
+        app.feed(&keys(":replay"));
+        app.feed(&[enter()]);
+        let toast = app.toast.as_ref().unwrap();
+        assert_eq!(toast.kind, ToastKind::Warn);
+        assert_eq!(toast.message, "Rhythm has no seed to replay · restarted");
+        assert_eq!(
+            app.active.as_ref().map(|game| game.kind()),
+            Some(GameKind::Rhythm)
+        );
+
+        app.start_typing_text("piped words only");
+        app.feed(&keys("pip"));
+        app.feed(&keys(":replay"));
+        app.feed(&[enter()]);
+        assert_eq!(app.toast.as_ref().unwrap().kind, ToastKind::Warn);
+        let screen = app.render_text(80, 20);
+        assert!(screen.contains("piped words only"), "{screen}");
+    }
+
+    #[test]
+    fn seed_command_reports_the_current_seed() {
+        let mut app = App::offline();
+        app.set_active(GameKind::AimTrainer.seeded_state(123, &app.config));
+        app.feed(&keys(":seed"));
+        app.feed(&[enter()]);
+        assert_eq!(app.toast.as_ref().unwrap().message, "Seed 123");
+
+        app.set_active(GameKind::Rhythm.new_state(&app.config));
+        app.feed(&keys(":seed"));
+        app.feed(&[enter()]);
+        assert_eq!(app.toast.as_ref().unwrap().message, "This game has no seed");
+    }
+
+    #[test]
+    fn empty_stdin_falls_back_to_random_words() {
+        let mut app = App::offline();
+        app.start_typing_text(" \n");
+        assert_eq!(app.toast.as_ref().unwrap().kind, ToastKind::Warn);
+        assert_eq!(
+            app.active.as_ref().map(|game| game.kind()),
+            Some(GameKind::Typing)
+        );
+    }
 }
@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use dirs::config_dir;
+use serde::{Deserialize, Serialize};
+
+use crate::games::GameKind;
+use crate::games::aim::AimPreset;
+use crate::games::sequence::SequencePace;
+use crate::games::verbal_memory::VerbalMode;
+use crate::menu::{ChartStyle, MenuSort};
+use crate::theme::Palette;
+
+/// User settings read from `config.toml`. Every field has a default, so a
+/// partial file only overrides the keys it names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Milliseconds between ticks of the main loop.
+    pub tick_rate_ms: u64,
+    /// Length of a Typing sprint in seconds.
+    pub typing_round_secs: u64,
+    /// Ring the terminal bell on GO, round ends, wrong answers and new bests.
+    pub sound_enabled: bool,
+    /// How long a freshly spawned Aim Trainer target pulses, in milliseconds.
+    pub aim_flash_ms: u64,
+    /// Aim Trainer board the game opens with: small, standard or large.
+    pub aim_preset: AimPreset,
+    /// Score Aim Trainer runs by time per cell travelled instead of total
+    /// time, so targets across the board count for more.
+    pub aim_weighted: bool,
+    /// How fast Sequence Memory flashes speed up: steady, normal or fast.
+    pub sequence_pace: SequencePace,
+    /// Flash every Sequence tile in the same color instead of one per cell.
+    pub sequence_monochrome: bool,
+    /// Pause before each new Sequence round is shown, in milliseconds.
+    pub sequence_ready_ms: u64,
+    /// How long Visual Memory shows each pattern, in milliseconds.
+    pub visual_reveal_ms: u64,
+    /// Verbal Memory lives in the normal mode.
+    pub verbal_lives: u8,
+    /// Play Verbal Memory without lives, ending after a fixed number of
+    /// answers and recording accuracy.
+    pub verbal_endless: bool,
+    /// Answers in an endless Verbal Memory run.
+    pub verbal_endless_judgments: u32,
+    /// Number Memory shows the number in groups of this many digits; 0
+    /// shows it unbroken.
+    pub number_group: usize,
+    /// Group the typed answer the same way.
+    pub number_group_input: bool,
+    /// Sweep a highlight across the digits while the number is shown, with
+    /// a tick per digit when sound is on.
+    pub number_metronome: bool,
+    /// Chimp Test hard mode: unpicked tiles vanish once input starts.
+    pub chimp_hard: bool,
+    /// Runs kept per game.
+    pub history_limit: usize,
+    /// Which runs go once a game has more than `history_limit`: the oldest
+    /// ones, or the oldest that are not a best.
+    pub history_trim: HistoryTrim,
+    /// Right/wrong feedback colors: default, colorblind or mono.
+    pub palette: Palette,
+    /// Plain ASCII grids and charts for terminals without Unicode glyphs.
+    pub ascii_only: bool,
+    /// Draw everything but cursors and highlights at reduced intensity.
+    pub dim: bool,
+    /// Set once the first-run tutorial has been finished or skipped.
+    pub tutorial_seen: bool,
+    /// The `[menu]` table.
+    pub menu: MenuConfig,
+}
+
+/// Which records go when a game's history is over `history_limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryTrim {
+    /// Drop the oldest records.
+    Oldest,
+    /// Drop the oldest records but never a best, one per label.
+    KeepBest,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MenuConfig {
+    /// Game list order, as set by `:sort`.
+    pub sort: MenuSort,
+    /// Games pinned to the top of the menu, by their `scores.json` key.
+    pub favorites: Vec<String>,
+    /// Score history chart: sparkline or braille.
+    pub chart: ChartStyle,
+    /// Game list width in percent, kept within 20–80.
+    pub list_percent: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tick_rate_ms: 50,
+            typing_round_secs: 30,
+            sound_enabled: false,
+            aim_flash_ms: 150,
+            aim_preset: AimPreset::Standard,
+            aim_weighted: false,
+            sequence_pace: SequencePace::Normal,
+            sequence_monochrome: false,
+            sequence_ready_ms: 700,
+            visual_reveal_ms: 2000,
+            verbal_lives: 3,
+            verbal_endless: false,
+            verbal_endless_judgments: 50,
+            number_group: 3,
+            number_group_input: true,
+            number_metronome: false,
+            chimp_hard: false,
+            history_limit: 64,
+            history_trim: HistoryTrim::KeepBest,
+            palette: Palette::Default,
+            ascii_only: false,
+            dim: false,
+            tutorial_seen: false,
+            menu: MenuConfig::default(),
+        }
+    }
+}
+
+impl Default for MenuConfig {
+    fn default() -> Self {
+        Self {
+            sort: MenuSort::Default,
+            favorites: Vec::new(),
+            chart: ChartStyle::Sparkline,
+            list_percent: 45,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config at `path`, writing the defaults there first if the
+    /// file does not exist yet. A file that fails to parse is left alone and
+    /// reported as an error so the caller can fall back to the defaults.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(_) => {
+                let config = Self::default();
+                config.save(path);
+                return Ok(config);
+            }
+        };
+        toml::from_str(&text).map_err(|err| format!("config.toml: {}", err.message()))
+    }
+
+    pub fn save(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return;
+        }
+        if let Ok(text) = toml::to_string_pretty(self) {
+            let _ = fs::write(path, text);
+        }
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms.max(1))
+    }
+
+    pub fn typing_round(&self) -> Duration {
+        Duration::from_secs(self.typing_round_secs.max(1))
+    }
+
+    pub fn sequence_ready(&self) -> Duration {
+        Duration::from_millis(self.sequence_ready_ms)
+    }
+
+    pub fn aim_flash(&self) -> Duration {
+        Duration::from_millis(self.aim_flash_ms)
+    }
+
+    pub fn visual_reveal(&self) -> Duration {
+        Duration::from_millis(self.visual_reveal_ms.max(100))
+    }
+
+    pub fn verbal_mode(&self) -> VerbalMode {
+        if self.verbal_endless {
+            VerbalMode::Endless {
+                judgments: self.verbal_endless_judgments.max(1),
+            }
+        } else {
+            VerbalMode::Lives(self.verbal_lives.max(1))
+        }
+    }
+
+    pub fn favorites(&self) -> impl Iterator<Item = GameKind> + '_ {
+        self.menu
+            .favorites
+            .iter()
+            .filter_map(|key| GameKind::from_persist_key(key))
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    let mut dir = config_dir()?;
+    dir.push("bored");
+    dir.push("config.toml");
+    Some(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_round_trip_through_toml() {
+        let text = toml::to_string_pretty(&Config::default()).unwrap();
+        assert_eq!(toml::from_str::<Config>(&text).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn partial_config_keeps_the_other_defaults() {
+        let config: Config = toml::from_str(
+            "tick_rate_ms = 20\nhistory_trim = \"oldest\"\n\n[menu]\nlist_percent = 30\n",
+        )
+        .unwrap();
+        assert_eq!(config.tick_rate(), Duration::from_millis(20));
+        assert_eq!(config.history_trim, HistoryTrim::Oldest);
+        assert_eq!(config.menu.list_percent, 30);
+        assert_eq!(config.menu.chart, ChartStyle::Sparkline);
+        assert_eq!(config.typing_round_secs, 30);
+        assert_eq!(config.verbal_lives, 3);
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dirs::config_dir;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::games::GameKind;
+use crate::games::aim::AimPreset;
+
+/// The three games every daily challenge is made of; only their order and
+/// seeds change from day to day.
+const LINEUP: [GameKind; 3] = [GameKind::Reaction, GameKind::Typing, GameKind::AimTrainer];
+
+/// A run through the day's games. Each game's first recorded result is
+/// scored out of 100 and the challenge score is the average.
+#[derive(Debug)]
+pub struct Daily {
+    day: u64,
+    games: [GameKind; 3],
+    scores: Vec<u32>,
+}
+
+impl Daily {
+    pub fn today() -> Self {
+        let day = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|dur| dur.as_secs() / 86_400)
+            .unwrap_or(0);
+        Self::for_day(day)
+    }
+
+    pub fn for_day(day: u64) -> Self {
+        let mut games = LINEUP;
+        games.shuffle(&mut StdRng::seed_from_u64(day));
+        Self {
+            day,
+            games,
+            scores: Vec::new(),
+        }
+    }
+
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    pub fn current(&self) -> Option<GameKind> {
+        self.games.get(self.scores.len()).copied()
+    }
+
+    /// 1-based position of the current game, for "Daily 2/3".
+    pub fn step(&self) -> usize {
+        self.scores.len() + 1
+    }
+
+    pub fn game_count(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Seed for the current game, the same for everyone on the same day.
+    pub fn seed(&self) -> u64 {
+        self.day
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .wrapping_add(self.scores.len() as u64)
+    }
+
+    /// Settings pinned so every player gets the same boards and round
+    /// lengths whatever their own config says.
+    pub fn config(base: &Config) -> Config {
+        Config {
+            aim_preset: AimPreset::Standard,
+            aim_weighted: false,
+            chimp_hard: false,
+            typing_round_secs: 30,
+            ..base.clone()
+        }
+    }
+
+    /// Scores the current game's result and moves on to the next one.
+    pub fn record(&mut self, kind: GameKind, score: f64) {
+        self.scores.push(normalize(kind, score));
+    }
+
+    pub fn total(&self) -> u32 {
+        if self.scores.is_empty() {
+            return 0;
+        }
+        self.scores.iter().sum::<u32>() / self.scores.len() as u32
+    }
+}
+
+/// Maps a raw result onto 0..=100 between a "slow" and a "great" value.
+fn normalize(kind: GameKind, score: f64) -> u32 {
+    let (worst, best) = match kind {
+        GameKind::Reaction => (500.0, 150.0),
+        GameKind::Typing => (0.0, 100.0),
+        GameKind::AimTrainer => (20_000.0, 3_000.0),
+        _ => return 0,
+    };
+    let fraction = ((score - worst) / (best - worst)).clamp(0.0, 1.0);
+    (fraction * 100.0).round() as u32
+}
+
+/// Best daily score so far, kept in `daily.json`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyBest {
+    pub score: u32,
+    pub day: u64,
+}
+
+impl DailyBest {
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(self) {
+            let _ = fs::write(path, json);
+        }
+    }
+}
+
+pub fn daily_path() -> Option<PathBuf> {
+    let mut dir = config_dir()?;
+    dir.push("bored");
+    dir.push("daily.json");
+    Some(dir)
+}
@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+const EVENT_WINDOW: Duration = Duration::from_secs(1);
+const OVERLAY_WIDTH: u16 = 26;
+const OVERLAY_HEIGHT: u16 = 5;
+
+/// Timing counters collected only while the `:debug` overlay is shown.
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    last_tick: Option<Instant>,
+    tick_interval: Duration,
+    render_time: Duration,
+    events: VecDeque<Instant>,
+}
+
+impl DebugStats {
+    pub fn record_tick(&mut self, now: Instant) {
+        if let Some(last) = self.last_tick {
+            self.tick_interval = now.saturating_duration_since(last);
+        }
+        self.last_tick = Some(now);
+    }
+
+    pub fn record_render(&mut self, elapsed: Duration) {
+        self.render_time = elapsed;
+    }
+
+    pub fn record_event(&mut self, now: Instant) {
+        self.events.push_back(now);
+        self.prune(now);
+    }
+
+    pub fn events_per_second(&mut self, now: Instant) -> usize {
+        self.prune(now);
+        self.events.len()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some(front) = self.events.front() {
+            if now.saturating_duration_since(*front) > EVENT_WINDOW {
+                self.events.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let width = OVERLAY_WIDTH.min(area.width);
+        let height = OVERLAY_HEIGHT.min(area.height);
+        let overlay = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height,
+        };
+        let lines = vec![
+            Line::from(format!(
+                "tick   {:>6.1} ms",
+                self.tick_interval.as_secs_f64() * 1000.0
+            )),
+            Line::from(format!(
+                "render {:>6.2} ms",
+                self.render_time.as_secs_f64() * 1000.0
+            )),
+            Line::from(format!(
+                "events {:>6}/s",
+                self.events_per_second(Instant::now())
+            )),
+        ];
+        frame.render_widget(Clear, overlay);
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title("Debug")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+            overlay,
+        );
+    }
+}
+
+/// Render times from `:bench`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchSummary {
+    pub samples: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl BenchSummary {
+    /// None when there is nothing to summarize.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let min = samples.iter().min().copied()?;
+        let max = samples.iter().max().copied()?;
+        let total: Duration = samples.iter().sum();
+        Some(Self {
+            samples: samples.len(),
+            min,
+            mean: total / samples.len() as u32,
+            max,
+        })
+    }
+
+    /// "50 renders · min 0.41 · mean 0.58 · max 1.20 ms".
+    pub fn describe(&self) -> String {
+        let ms = |time: Duration| time.as_secs_f64() * 1000.0;
+        format!(
+            "{} renders · min {:.2} · mean {:.2} · max {:.2} ms",
+            self.samples,
+            ms(self.min),
+            ms(self.mean),
+            ms(self.max)
+        )
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dirs::config_dir;
+
+/// Appends a timestamped line to `errors.log` beside the scores, so crashes
+/// and failed saves survive the alternate screen. Best-effort: a log that
+/// cannot be written is silently skipped.
+pub fn append(message: &str) {
+    let Some(path) = log_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent()
+        && fs::create_dir_all(parent).is_err()
+    {
+        return;
+    }
+    let at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs())
+        .unwrap_or(0);
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", format_line(at, message));
+    }
+}
+
+/// One log entry; newlines in `message` are flattened so each entry stays
+/// on a single line.
+fn format_line(at: u64, message: &str) -> String {
+    format!("[{at}] {}", message.replace('\n', " | "))
+}
+
+fn log_path() -> Option<PathBuf> {
+    let mut dir = config_dir()?;
+    dir.push("bored");
+    dir.push("errors.log");
+    Some(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_message_becomes_one_log_line() {
+        assert_eq!(
+            format_line(
+                1_700_000_000,
+                "panic: panicked at src/app.rs:10:5:\nindex out of bounds"
+            ),
+            "[1700000000] panic: panicked at src/app.rs:10:5: | index out of bounds"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::games::{GameKind, ScoreDirection, StatRecord};
+
+/// Bumped whenever the exported layout changes in a way consumers can see.
+const EXPORT_VERSION: u32 = 1;
+
+// The export has its own types on purpose: `scores.json` follows the serde
+// derives on the internal types, while this schema only changes with
+// `EXPORT_VERSION`.
+#[derive(Serialize)]
+struct ExportDocument {
+    version: u32,
+    games: Vec<ExportGame>,
+}
+
+#[derive(Serialize)]
+struct ExportGame {
+    slug: String,
+    title: &'static str,
+    better: &'static str,
+    records: Vec<ExportRecord>,
+}
+
+#[derive(Serialize)]
+struct ExportRecord {
+    label: String,
+    value: String,
+    score: f64,
+    /// ISO-8601 in UTC, or null for legacy records without a timestamp.
+    recorded_at: Option<String>,
+}
+
+/// Writes every game's history to `path` and returns the number of records.
+pub fn write_json(path: &Path, stats: &HashMap<GameKind, Vec<StatRecord>>) -> io::Result<usize> {
+    let (json, count) = to_json(stats).map_err(io::Error::other)?;
+    fs::write(path, json)?;
+    Ok(count)
+}
+
+/// The export document as pretty JSON, with the number of records in it.
+pub fn to_json(stats: &HashMap<GameKind, Vec<StatRecord>>) -> serde_json::Result<(Vec<u8>, usize)> {
+    let mut count = 0;
+    let games = GameKind::ALL
+        .iter()
+        .map(|kind| {
+            let records: Vec<ExportRecord> = stats
+                .get(kind)
+                .map(|history| {
+                    history
+                        .iter()
+                        .map(|record| ExportRecord {
+                            label: record.label.clone(),
+                            value: record.value.clone(),
+                            score: record.score,
+                            recorded_at: (record.recorded_at != 0)
+                                .then(|| iso8601(record.recorded_at))
+                                .flatten(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            count += records.len();
+            ExportGame {
+                slug: kind.slug(),
+                title: kind.title(),
+                better: match kind.score_direction() {
+                    ScoreDirection::HigherIsBetter => "higher",
+                    ScoreDirection::LowerIsBetter => "lower",
+                },
+                records,
+            }
+        })
+        .collect();
+    let document = ExportDocument {
+        version: EXPORT_VERSION,
+        games,
+    };
+    Ok((serde_json::to_vec_pretty(&document)?, count))
+}
+
+/// Formats seconds since the Unix epoch as `YYYY-MM-DDTHH:MM:SSZ`, or
+/// `None` past the range chrono can represent.
+fn iso8601(secs: u64) -> Option<String> {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_parses_with_a_version_and_every_record() {
+        let mut old = StatRecord::new("Reaction", "230 ms", 230.0);
+        old.recorded_at = 1_700_000_000;
+        let stats = HashMap::from([
+            (
+                GameKind::Reaction,
+                vec![old, StatRecord::new("Reaction", "210 ms", 210.0)],
+            ),
+            (GameKind::Sequence, vec![StatRecord::new("Level", "9", 9.0)]),
+        ]);
+        let (json, count) = to_json(&stats).unwrap();
+        assert_eq!(count, 3);
+
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["version"], EXPORT_VERSION);
+        let games = value["games"].as_array().unwrap();
+        assert_eq!(games.len(), GameKind::ALL.len());
+        let records: usize = games
+            .iter()
+            .map(|game| game["records"].as_array().unwrap().len())
+            .sum();
+        assert_eq!(records, 3);
+        assert_eq!(
+            games[0]["records"][0]["recorded_at"],
+            "2023-11-14T22:13:20Z"
+        );
+    }
+}
@@ -1,53 +1,244 @@
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{Event, KeyCode};
 use rand::{Rng, SeedableRng, rngs::StdRng};
 use ratatui::prelude::*;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
+use serde::{Deserialize, Serialize};
 
-use super::{GameAction, GameKind, StatRecord, navigation::VimMotionState};
+use super::{Game, GameAction, GameKind, StatRecord, navigation::VimMotionState};
+use crate::theme;
 
-const GRID: usize = 16;
-const TARGETS: u32 = 10;
+const TARGET_FLASH: Duration = Duration::from_millis(150);
+
+/// Board size and run length. Bests are kept per preset since a run on a
+/// small board is not comparable to one on a large board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AimPreset {
+    /// 8×8 grid, 5 targets.
+    Small,
+    /// 16×16 grid, 10 targets.
+    Standard,
+    /// 24×24 grid, 15 targets.
+    Large,
+}
+
+impl AimPreset {
+    fn grid(self) -> usize {
+        match self {
+            AimPreset::Small => 8,
+            AimPreset::Standard => 16,
+            AimPreset::Large => 24,
+        }
+    }
+
+    fn targets(self) -> u32 {
+        match self {
+            AimPreset::Small => 5,
+            AimPreset::Standard => 10,
+            AimPreset::Large => 15,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AimPreset::Small => "small",
+            AimPreset::Standard => "standard",
+            AimPreset::Large => "large",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            AimPreset::Small => AimPreset::Standard,
+            AimPreset::Standard => AimPreset::Large,
+            AimPreset::Large => AimPreset::Small,
+        }
+    }
+
+    /// Standard keeps the plain label so existing histories line up.
+    fn record_label(self, weighted: bool) -> String {
+        let base = if weighted { "WeightedScore" } else { "Total" };
+        match self {
+            AimPreset::Standard => base.into(),
+            other => format!("{base} ({})", other.label()),
+        }
+    }
+}
+
+/// Weight of a target: the Manhattan distance the crosshair had to travel
+/// from where it stood when the target appeared. Never below one so a
+/// target spawning under the cursor still counts.
+fn target_weight(from: (usize, usize), to: (usize, usize)) -> f64 {
+    (from.0.abs_diff(to.0) + from.1.abs_diff(to.1)).max(1) as f64
+}
 
 #[derive(Debug)]
 pub struct AimTrainerState {
+    preset: AimPreset,
+    grid: usize,
+    targets: u32,
     cursor: (usize, usize),
     target: (usize, usize),
     hits: u32,
     total_time: Duration,
+    /// Where the crosshair stood when the current target spawned.
+    spawn_from: (usize, usize),
+    /// Sum of each target's time in ms divided by its weight.
+    weighted_ms: f64,
+    weighted: bool,
+    first_move_total: Duration,
+    first_move: Option<Duration>,
+    last_split: Option<(Duration, Duration)>,
+    flash: Duration,
     spawn: Instant,
-    run_start: Instant,
+    run_start: Option<Instant>,
+    seed: u64,
     rng: StdRng,
     finished: bool,
-    best_total_ms: Option<f64>,
+    best_total_ms: HashMap<(AimPreset, bool), f64>,
     status: String,
     nav: VimMotionState,
 }
 
 impl AimTrainerState {
     pub fn new() -> Self {
-        let mut rng = StdRng::from_entropy();
-        let target = (rng.gen_range(0..GRID), rng.gen_range(0..GRID));
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let preset = AimPreset::Standard;
+        let grid = preset.grid();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let target = (rng.gen_range(0..grid), rng.gen_range(0..grid));
         Self {
-            cursor: (GRID / 2, GRID / 2),
+            seed,
+            preset,
+            grid,
+            targets: preset.targets(),
+            cursor: (grid / 2, grid / 2),
             target,
             hits: 0,
             total_time: Duration::ZERO,
+            spawn_from: (grid / 2, grid / 2),
+            weighted_ms: 0.0,
+            weighted: false,
+            first_move_total: Duration::ZERO,
+            first_move: None,
+            last_split: None,
+            flash: TARGET_FLASH,
             spawn: Instant::now(),
-            run_start: Instant::now(),
+            run_start: None,
             rng,
             finished: false,
-            best_total_ms: None,
-            status: "Move with hjkl · counts + 0/$/gg/G work".into(),
+            best_total_ms: HashMap::new(),
+            status: "Move with hjkl · counts + 0/$/w/b/gg/G work · p: board size · s: scoring"
+                .into(),
             nav: VimMotionState::default(),
         }
     }
 
+    pub fn with_flash(mut self, flash: Duration) -> Self {
+        self.flash = flash;
+        self
+    }
+
+    pub fn with_weighted(mut self, weighted: bool) -> Self {
+        self.weighted = weighted;
+        self
+    }
+
+    /// Resizes the board; the first target is drawn again from the seed so
+    /// a seeded replay on the same preset sees the same targets.
+    pub fn with_preset(mut self, preset: AimPreset) -> Self {
+        self.preset = preset;
+        self.grid = preset.grid();
+        self.targets = preset.targets();
+        self.cursor = (self.grid / 2, self.grid / 2);
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.spawn_target();
+        self
+    }
+
+    /// Starts a fresh run on `preset`, keeping the scoring mode and the
+    /// per-preset bests.
+    fn new_run(&mut self, preset: AimPreset) {
+        let best_total_ms = std::mem::take(&mut self.best_total_ms);
+        *self = Self::new()
+            .with_flash(self.flash)
+            .with_preset(preset)
+            .with_weighted(self.weighted);
+        self.best_total_ms = best_total_ms;
+    }
+
+    fn toggle_scoring(&mut self) {
+        self.weighted = !self.weighted;
+        self.new_run(self.preset);
+        self.status = if self.weighted {
+            "Scoring: ms per cell travelled · far targets count for more".into()
+        } else {
+            "Scoring: total time".into()
+        };
+    }
+
+    fn scoring_label(&self) -> &'static str {
+        if self.weighted { "weighted" } else { "timed" }
+    }
+
+    fn cycle_preset(&mut self) {
+        self.new_run(self.preset.next());
+        self.status = format!(
+            "Board {} · {}×{} · {} targets",
+            self.preset.label(),
+            self.grid,
+            self.grid,
+            self.targets
+        );
+    }
+
+    /// Starts the run clock on the first key so time spent reading the
+    /// instructions is not charged to the first target.
+    fn start_clock(&mut self) {
+        if self.run_start.is_none() {
+            let now = Instant::now();
+            self.run_start = Some(now);
+            self.spawn = now;
+        }
+    }
+
+    fn run_elapsed(&self) -> Duration {
+        self.run_start
+            .map(|start| Instant::now() - start)
+            .unwrap_or_default()
+    }
+
     fn spawn_target(&mut self) {
-        self.target = (self.rng.gen_range(0..GRID), self.rng.gen_range(0..GRID));
+        self.spawn_from = self.cursor;
+        self.target = (
+            self.rng.gen_range(0..self.grid),
+            self.rng.gen_range(0..self.grid),
+        );
         self.spawn = Instant::now();
+        self.first_move = None;
+    }
+
+    /// Time from the target appearing to the first motion key, i.e. how long
+    /// it took to find it before moving.
+    fn mark_first_move(&mut self) {
+        if self.first_move.is_none() {
+            self.first_move = Some(Instant::now() - self.spawn);
+        }
+    }
+
+    fn is_flashing(&self) -> bool {
+        self.run_start.is_some() && !self.finished && self.spawn.elapsed() < self.flash
+    }
+
+    fn average_first_move_ms(&self) -> Option<f64> {
+        (self.hits > 0).then(|| self.first_move_total.as_secs_f64() * 1000.0 / self.hits as f64)
     }
 
     fn tag(&mut self) -> GameAction {
@@ -56,29 +247,50 @@ impl AimTrainerState {
         }
         if self.cursor == self.target {
             let elapsed = Instant::now() - self.spawn;
+            let first_move = self.first_move.unwrap_or(elapsed);
             self.total_time += elapsed;
+            self.weighted_ms +=
+                elapsed.as_secs_f64() * 1000.0 / target_weight(self.spawn_from, self.target);
+            self.first_move_total += first_move;
+            self.last_split = Some((first_move, elapsed));
             self.hits += 1;
-            if self.hits == TARGETS {
+            if self.hits == self.targets {
                 self.finished = true;
                 let total_ms = self.total_time.as_secs_f64() * 1000.0;
+                let (score, value) = if self.weighted {
+                    let per_cell = self.weighted_ms / self.targets as f64;
+                    (per_cell, format!("{per_cell:.0} ms/cell"))
+                } else {
+                    (total_ms, format!("{total_ms:.0} ms"))
+                };
+                let weighted = if self.weighted {
+                    format!(" · weighted {value}")
+                } else {
+                    String::new()
+                };
                 self.status = format!(
-                    "Complete! total {:.0} ms (avg {:.0} ms)",
+                    "Complete! total {:.0} ms (avg {:.0} ms · first move avg {:.0} ms){weighted} · r to restart",
                     total_ms,
-                    total_ms / TARGETS as f64
+                    total_ms / self.targets as f64,
+                    self.average_first_move_ms().unwrap_or(0.0)
                 );
                 if self
                     .best_total_ms
-                    .map(|best| total_ms < best)
-                    .unwrap_or(true)
+                    .get(&(self.preset, self.weighted))
+                    .is_none_or(|&best| score < best)
                 {
-                    self.best_total_ms = Some(total_ms);
-                    return GameAction::Record(
-                        StatRecord::new("Total", format!("{total_ms:.0} ms"), total_ms),
-                        GameKind::AimTrainer,
-                    );
+                    self.best_total_ms
+                        .insert((self.preset, self.weighted), score);
+                    let record =
+                        StatRecord::new(self.preset.record_label(self.weighted), value, score)
+                            .with_note(format!(
+                                "first move avg {:.0} ms",
+                                self.average_first_move_ms().unwrap_or(0.0)
+                            ));
+                    return GameAction::Record(record, GameKind::AimTrainer);
                 }
             } else {
-                self.status = format!("Target {}/{}", self.hits + 1, TARGETS);
+                self.status = format!("Target {}/{}", self.hits + 1, self.targets);
                 self.spawn_target();
             }
         } else {
@@ -86,8 +298,18 @@ impl AimTrainerState {
         }
         GameAction::None
     }
+}
+
+impl Game for AimTrainerState {
+    fn kind(&self) -> GameKind {
+        GameKind::AimTrainer
+    }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Aim Trainer")
             .borders(Borders::ALL)
@@ -98,11 +320,15 @@ impl AimTrainerState {
         let elapsed_ms = if self.finished {
             self.total_time.as_secs_f64() * 1000.0
         } else {
-            (Instant::now() - self.run_start).as_secs_f64() * 1000.0
+            self.run_elapsed().as_secs_f64() * 1000.0
         };
         let mut lines = vec![Line::from(format!(
-            "Hits: {}/{} · Elapsed {:.0} ms",
-            self.hits, TARGETS, elapsed_ms
+            "Hits: {}/{} · Elapsed {:.0} ms · {} board · {}",
+            self.hits,
+            self.targets,
+            elapsed_ms,
+            self.preset.label(),
+            self.scoring_label()
         ))];
         let status_text = if let Some(count) = self.nav.prefix() {
             format!("{} · count {}", self.status, count)
@@ -110,36 +336,84 @@ impl AimTrainerState {
             self.status.clone()
         };
         lines.push(Line::from(status_text));
-        if let Some(best) = self.best_total_ms {
-            lines.push(Line::from(format!("Best run: {:.0} ms", best)));
+        if let Some((first_move, total)) = self.last_split {
+            lines.push(Line::from(format!(
+                "Last target: first move {:.0} ms · total {:.0} ms",
+                first_move.as_secs_f64() * 1000.0,
+                total.as_secs_f64() * 1000.0
+            )));
+        }
+        if let Some(best) = self.best_total_ms.get(&(self.preset, self.weighted)) {
+            let unit = if self.weighted { "ms/cell" } else { "ms" };
+            lines.push(Line::from(format!("Best run: {best:.0} {unit}")));
+        }
+        // Large boards can outgrow the terminal; say so instead of silently
+        // clipping the target out of view.
+        if (lines.len() + self.grid) as u16 > inner.height || (self.grid * 2) as u16 > inner.width {
+            lines.push(Line::styled(
+                "Board does not fit · enlarge the terminal or pick a smaller board with p",
+                Style::default().fg(Color::Yellow),
+            ));
         }
 
+        let pulse = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let flashing = self.is_flashing();
         let mut grid_lines = Vec::new();
-        for y in 0..GRID {
-            let mut row = String::new();
-            for x in 0..GRID {
-                if (x, y) == self.cursor {
+        for y in 0..self.grid {
+            let mut spans = Vec::with_capacity(self.grid);
+            for x in 0..self.grid {
+                let glyph = if (x, y) == self.cursor {
                     if (x, y) == self.target {
-                        row.push('✚');
+                        theme::glyph("✚ ", "X ")
                     } else {
-                        row.push('⌖');
+                        theme::glyph("⌖ ", "+ ")
                     }
                 } else if (x, y) == self.target {
-                    row.push('●');
+                    theme::glyph("● ", "O ")
+                } else {
+                    theme::glyph("· ", ". ")
+                };
+                if flashing && (x, y) == self.target {
+                    spans.push(Span::styled(glyph, pulse));
                 } else {
-                    row.push('·');
+                    spans.push(Span::raw(glyph));
                 }
-                row.push(' ');
             }
-            grid_lines.push(Line::from(row));
+            grid_lines.push(Line::from(spans));
         }
         lines.extend(grid_lines);
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
-            if self.nav.handle_key(key, &mut self.cursor, GRID, GRID) {
+            // The board and scoring only change between runs.
+            if self.run_start.is_none() || self.finished {
+                match key.code {
+                    KeyCode::Char('p') => {
+                        self.cycle_preset();
+                        return GameAction::None;
+                    }
+                    KeyCode::Char('s') => {
+                        self.toggle_scoring();
+                        return GameAction::None;
+                    }
+                    _ => {}
+                }
+            }
+            self.start_clock();
+            let target = self.target;
+            let occupied = |x, y| (x, y) == target;
+            if self
+                .nav
+                .handle_key(key, &mut self.cursor, self.grid, self.grid, Some(&occupied))
+            {
+                if !self.finished {
+                    self.mark_first_move();
+                }
                 return GameAction::None;
             }
 
@@ -155,21 +429,21 @@ impl AimTrainerState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, _now: Instant) -> GameAction {
+    fn handle_tick(&mut self, _now: Instant) -> GameAction {
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
+    fn status_line(&self) -> String {
         let base = if self.finished {
             self.status.clone()
         } else {
             format!(
                 "Target {}/{} · cursor ({}, {}) · elapsed {:.1}s",
                 self.hits + 1,
-                TARGETS,
+                self.targets,
                 self.cursor.0 + 1,
                 self.cursor.1 + 1,
-                (Instant::now() - self.run_start).as_secs_f64()
+                self.run_elapsed().as_secs_f64()
             )
         };
         if let Some(count) = self.nav.prefix() {
@@ -178,4 +452,119 @@ impl AimTrainerState {
             base
         }
     }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        self.spawn += paused;
+        if let Some(start) = &mut self.run_start {
+            *start += paused;
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !self.finished {
+            return false;
+        }
+        self.new_run(self.preset);
+        true
+    }
+
+    fn is_animating(&self) -> bool {
+        self.run_start.is_some() && !self.finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn idle_time_before_the_first_key_is_not_counted() {
+        let mut state = AimTrainerState::with_seed(7);
+        state.spawn -= Duration::from_secs(2);
+        state.cursor = state.target;
+        state.handle_event(&key(KeyCode::Enter));
+        assert_eq!(state.hits, 1);
+        assert!(state.total_time < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn first_move_runs_from_spawn_to_the_first_motion() {
+        let mut state = AimTrainerState::with_seed(7);
+        state.targets = 1;
+        state.target = (0, 0);
+        state.handle_event(&key(KeyCode::Char('l')));
+        state.spawn -= Duration::from_millis(300);
+        state.first_move = None;
+        state.handle_event(&key(KeyCode::Char('h')));
+        let first = state.first_move.expect("the motion was timed");
+        assert!(first >= Duration::from_millis(300) && first < Duration::from_secs(1));
+        state.handle_event(&key(KeyCode::Char('h')));
+        assert_eq!(state.first_move, Some(first));
+
+        state.cursor = state.target;
+        match state.handle_event(&key(KeyCode::Enter)) {
+            GameAction::Record(record, _) => {
+                assert!(record.note.unwrap().starts_with("first move avg 3"));
+            }
+            _ => panic!("the only target finishes the run"),
+        }
+    }
+
+    #[test]
+    fn large_board_targets_stay_on_the_grid() {
+        let mut state = AimTrainerState::with_seed(11).with_preset(AimPreset::Large);
+        assert_eq!(state.grid, 24);
+        for _ in 0..500 {
+            state.spawn_target();
+            assert!(state.target.0 < 24 && state.target.1 < 24);
+        }
+    }
+
+    #[test]
+    fn each_preset_records_under_its_own_label() {
+        let labels: Vec<String> = [AimPreset::Small, AimPreset::Standard, AimPreset::Large]
+            .into_iter()
+            .map(|preset| preset.record_label(false))
+            .collect();
+        assert_eq!(labels, ["Total (small)", "Total", "Total (large)"]);
+    }
+
+    #[test]
+    fn far_targets_weigh_more_than_adjacent_ones() {
+        let adjacent = target_weight((5, 5), (6, 5));
+        let far = target_weight((0, 0), (15, 15));
+        assert_eq!(adjacent, 1.0);
+        assert_eq!(far, 30.0);
+        assert_eq!(target_weight((3, 3), (3, 3)), 1.0);
+    }
+
+    #[test]
+    fn weighted_runs_record_under_their_own_label() {
+        assert_eq!(AimPreset::Standard.record_label(true), "WeightedScore");
+        assert_ne!(
+            AimPreset::Standard.record_label(true),
+            AimPreset::Standard.record_label(false)
+        );
+    }
+
+    #[test]
+    fn dollar_jumps_to_the_last_column() {
+        let mut state = AimTrainerState::with_seed(3);
+        let row = state.cursor.1;
+        state.handle_event(&key(KeyCode::Char('$')));
+        assert_eq!(state.cursor, (state.grid - 1, row));
+        state.handle_event(&key(KeyCode::Char('0')));
+        assert_eq!(state.cursor, (0, row));
+        state.handle_event(&key(KeyCode::Char('G')));
+        assert_eq!(state.cursor, (0, state.grid - 1));
+
+        state.handle_event(&key(KeyCode::Char('3')));
+        assert!(state.status_line().contains("count 3"));
+    }
 }
@@ -5,13 +5,27 @@ use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
+use serde::{Deserialize, Serialize};
 
-use super::{GameAction, GameKind, StatRecord, navigation::VimMotionState};
+use super::{
+    Game, GameAction, GameKind, Snapshot, StatRecord, Streak,
+    navigation::{VimMotionState, relative_gutter},
+};
+use crate::theme;
 
 const GRID: usize = 10;
 const BASE_NUMBERS: u8 = 4;
 const REVEAL: Duration = Duration::from_secs(2);
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChimpSnapshot {
+    seed: u64,
+    level: u8,
+    best: u8,
+    #[serde(default)]
+    hard: bool,
+}
+
 #[derive(Debug)]
 pub struct ChimpTestState {
     tiles: Vec<Tile>,
@@ -19,10 +33,21 @@ pub struct ChimpTestState {
     next_value: u8,
     level: u8,
     best: u8,
+    seed: u64,
     rng: StdRng,
     phase: Phase,
     status: String,
     numbers_hidden: bool,
+    /// Training aid: numbers stay visible after the first pick and nothing
+    /// is recorded.
+    learning: bool,
+    /// Hard mode: once input starts, unpicked tiles are drawn as empty
+    /// cells, leaving only picked ones and the cursor on the board.
+    hard: bool,
+    /// When the numbers were hidden and input opened for the current board.
+    input_start: Option<Instant>,
+    last_clear: Option<Duration>,
+    streak: Streak,
     nav: VimMotionState,
 }
 
@@ -42,8 +67,13 @@ enum Phase {
 
 impl ChimpTestState {
     pub fn new() -> Self {
-        let rng = StdRng::from_entropy();
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
         let mut state = Self {
+            seed,
             tiles: Vec::new(),
             cursor: (0, 0),
             next_value: 1,
@@ -55,12 +85,36 @@ impl ChimpTestState {
             },
             status: "Memorize the numbers".into(),
             numbers_hidden: false,
+            learning: false,
+            hard: false,
+            input_start: None,
+            last_clear: None,
+            streak: Streak::default(),
             nav: VimMotionState::default(),
         };
         state.generate_tiles();
         state
     }
 
+    /// Resumes at the saved level with a fresh board.
+    pub fn restore(snapshot: ChimpSnapshot) -> Self {
+        let mut state = Self::with_seed(snapshot.seed);
+        state.level = snapshot.level;
+        state.best = snapshot.best;
+        state.hard = snapshot.hard;
+        state.generate_tiles();
+        state
+    }
+
+    pub fn with_hard(mut self, hard: bool) -> Self {
+        self.hard = hard;
+        self
+    }
+
+    fn record_label(&self) -> &'static str {
+        if self.hard { "Level (hard)" } else { "Level" }
+    }
+
     fn generate_tiles(&mut self) {
         self.tiles.clear();
         self.next_value = 1;
@@ -86,6 +140,7 @@ impl ChimpTestState {
             start: Instant::now(),
         };
         self.status = format!("Level {} · remember the order", self.level);
+        self.input_start = None;
         self.nav.clear();
     }
 
@@ -99,22 +154,44 @@ impl ChimpTestState {
             if value == self.next_value {
                 self.tiles[idx].cleared = true;
                 self.next_value += 1;
-                if value == 1 {
+                if value == 1 && !self.learning {
                     self.numbers_hidden = true;
                 }
                 if self.tiles.iter().all(|t| t.cleared) {
+                    let clear = self.input_start.map(|start| start.elapsed());
+                    self.last_clear = clear;
                     self.level += 1;
+                    if self.learning {
+                        self.generate_tiles();
+                        return GameAction::None;
+                    }
+                    self.streak.hit();
                     if self.level > self.best {
                         self.best = self.level;
-                        let record =
-                            StatRecord::new("Level", self.best.to_string(), self.best as f64);
+                        // Level stays the score; the clear time rides along
+                        // in the note to tell equal levels apart.
+                        let mut record = StatRecord::new(
+                            self.record_label(),
+                            self.best.to_string(),
+                            self.best as f64,
+                        );
+                        if let Some(clear) = clear {
+                            record =
+                                record.with_note(format!("cleared in {:.1}s", clear.as_secs_f64()));
+                        }
                         self.generate_tiles();
                         return GameAction::Record(record, GameKind::ChimpTest);
                     }
                     self.generate_tiles();
                 }
             } else {
-                self.status = format!("Missed! the next number was {}", self.next_value);
+                if !self.learning {
+                    self.streak.miss();
+                }
+                self.status = format!(
+                    "Missed! the next number was {} · r to restart",
+                    self.next_value
+                );
                 self.phase = Phase::Result;
                 self.level = 1;
             }
@@ -122,7 +199,60 @@ impl ChimpTestState {
         GameAction::None
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    /// Switching modes starts over at level 1 so a learning run can never
+    /// carry into scored play.
+    fn toggle_learning(&mut self) {
+        self.learning = !self.learning;
+        self.hard = false;
+        self.level = 1;
+        self.generate_tiles();
+        if self.learning {
+            self.status = "Learning mode · numbers stay visible · not recorded".into();
+        }
+    }
+
+    /// Like learning mode, switching starts over, and the session best goes
+    /// with it since levels from the two modes are not comparable. So does
+    /// the streak, which is recorded first.
+    fn toggle_hard(&mut self) -> GameAction {
+        let streak = self.streak.take_record(GameKind::ChimpTest);
+        self.hard = !self.hard;
+        self.learning = false;
+        self.level = 1;
+        self.best = 0;
+        self.generate_tiles();
+        self.status = if self.hard {
+            "Hard mode · tiles vanish once you start picking".into()
+        } else {
+            "Hard mode off".into()
+        };
+        streak
+    }
+
+    /// "Board 3.4s · last clear 5.1s", with the running time only while
+    /// picking tiles.
+    fn timing_text(&self) -> String {
+        let mut parts = Vec::new();
+        if let (Phase::Input, Some(start)) = (self.phase, self.input_start) {
+            parts.push(format!("Board {:.1}s", start.elapsed().as_secs_f64()));
+        }
+        if let Some(clear) = self.last_clear {
+            parts.push(format!("last clear {:.1}s", clear.as_secs_f64()));
+        }
+        parts.join(" · ")
+    }
+}
+
+impl Game for ChimpTestState {
+    fn kind(&self) -> GameKind {
+        GameKind::ChimpTest
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Chimp Test")
             .borders(Borders::ALL)
@@ -131,13 +261,29 @@ impl ChimpTestState {
         frame.render_widget(block, area);
 
         let mut lines = vec![Line::from(format!(
-            "Level {} (best {})",
-            self.level, self.best
+            "Level {} (best {}){}",
+            self.level,
+            self.best,
+            if self.learning {
+                " · learning"
+            } else if self.hard {
+                " · hard"
+            } else {
+                ""
+            }
         ))];
         lines.push(Line::from(self.status.as_str()));
+        lines.push(Line::from(self.timing_text()));
 
-        for y in 0..GRID {
-            let mut spans = Vec::with_capacity(GRID * 2);
+        let gutter = relative_gutter(self.cursor.1, GRID);
+        for (y, number) in gutter.into_iter().enumerate() {
+            let mut spans = Vec::with_capacity(GRID * 2 + 1);
+            let gutter_style = if y == self.cursor.1 {
+                theme::highlight(Style::default().fg(Color::Yellow))
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(number, gutter_style));
             for x in 0..GRID {
                 let cell_text = if let Some(tile) = self.tiles.iter().find(|t| t.pos == (x, y)) {
                     let numbers_visible = matches!(self.phase, Phase::Reveal { .. })
@@ -146,6 +292,8 @@ impl ChimpTestState {
                         format!("{:>3}", tile.value)
                     } else if tile.cleared {
                         " ✓ ".into()
+                    } else if self.hard {
+                        "   ".into()
                     } else {
                         " ■ ".into()
                     }
@@ -167,9 +315,17 @@ impl ChimpTestState {
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
-            if self.nav.handle_key(key, &mut self.cursor, GRID, GRID) {
+            let tiles = &self.tiles;
+            let occupied = |x, y| tiles.iter().any(|t| t.pos == (x, y) && !t.cleared);
+            // Word motions jump between tiles, which would give their
+            // places away in hard mode.
+            let occupied: Option<&dyn Fn(usize, usize) -> bool> = (!self.hard).then_some(&occupied);
+            if self
+                .nav
+                .handle_key(key, &mut self.cursor, GRID, GRID, occupied)
+            {
                 return GameAction::None;
             }
 
@@ -183,6 +339,8 @@ impl ChimpTestState {
                     }
                 }
                 KeyCode::Char(' ') => return self.select(),
+                KeyCode::Char('t') => self.toggle_learning(),
+                KeyCode::Char('x') => return self.toggle_hard(),
                 KeyCode::Esc => self.nav.clear(),
                 _ => {}
             }
@@ -190,22 +348,169 @@ impl ChimpTestState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Phase::Reveal { start } = self.phase {
-            if now.duration_since(start) >= REVEAL {
-                self.phase = Phase::Input;
-                self.status = "Select numbers in order".into();
-            }
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        if let Phase::Reveal { start } = self.phase
+            && now.duration_since(start) >= REVEAL
+        {
+            self.phase = Phase::Input;
+            self.input_start = Some(now);
+            self.numbers_hidden = self.hard;
+            self.status = "Select numbers in order · t learning mode · x hard mode".into();
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
-        let base = format!("Level {} · Next {}", self.level, self.next_value);
+    fn status_line(&self) -> String {
+        let base = format!(
+            "Level {} · Next {} · streak {}",
+            self.level,
+            self.next_value,
+            self.streak.current()
+        );
         if let Some(count) = self.nav.prefix() {
             format!("{} · count {}", base, count)
         } else {
             base
         }
     }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Phase::Reveal { start } = &mut self.phase {
+            *start += paused;
+        }
+        if let Some(start) = &mut self.input_start {
+            *start += paused;
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !matches!(self.phase, Phase::Result) {
+            return false;
+        }
+        self.level = 1;
+        self.generate_tiles();
+        true
+    }
+
+    fn end_session(&mut self) -> GameAction {
+        self.streak.take_record(GameKind::ChimpTest)
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Reveal { .. } | Phase::Input)
+    }
+
+    fn snapshot(&self) -> Option<Snapshot> {
+        if self.level <= 1 || self.learning || matches!(self.phase, Phase::Result) {
+            return None;
+        }
+        Some(Snapshot::ChimpTest(ChimpSnapshot {
+            seed: self.seed,
+            level: self.level,
+            best: self.best,
+            hard: self.hard,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::STREAK_LABEL;
+    use super::*;
+
+    /// Opens input on the current board as if the reveal had ended `ago`.
+    fn open_input(state: &mut ChimpTestState, ago: Duration) {
+        let now = Instant::now();
+        state.phase = Phase::Reveal {
+            start: now - REVEAL - ago,
+        };
+        state.handle_tick(now - ago);
+    }
+
+    /// Picks every tile in order and returns what the last pick gave.
+    fn clear_board(state: &mut ChimpTestState) -> GameAction {
+        let mut positions: Vec<_> = state.tiles.iter().map(|t| (t.value, t.pos)).collect();
+        positions.sort();
+        let mut action = GameAction::None;
+        for (_, pos) in positions {
+            state.cursor = pos;
+            action = state.select();
+        }
+        action
+    }
+
+    #[test]
+    fn clear_time_is_recorded_with_a_new_best() {
+        let mut state = ChimpTestState::with_seed(3);
+        open_input(&mut state, Duration::from_secs(2));
+        match clear_board(&mut state) {
+            GameAction::Record(record, GameKind::ChimpTest) => {
+                assert_eq!(record.value, "2");
+                assert_eq!(record.score, 2.0);
+                let note = record.note.expect("the clear time is kept");
+                assert!(note.starts_with("cleared in 2."), "{note}");
+            }
+            _ => panic!("clearing the first board is a new best"),
+        }
+    }
+
+    #[test]
+    fn hard_mode_hides_tiles_during_input() {
+        let mut state = ChimpTestState::with_seed(8).with_hard(true);
+        open_input(&mut state, Duration::ZERO);
+        assert!(matches!(state.phase, Phase::Input));
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(40, 16)).unwrap();
+        terminal
+            .draw(|frame| state.render(frame, frame.size()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        // Rows below the three header lines and the border hold the board.
+        let board: String = (4..14)
+            .flat_map(|y| (3..buffer.area.width - 1).map(move |x| (x, y)))
+            .map(|(x, y)| buffer.get(x, y).symbol().to_string())
+            .collect();
+        assert!(!board.contains('■'));
+        assert!(!board.chars().any(|ch| ch.is_ascii_digit()), "{board}");
+    }
+
+    #[test]
+    fn switching_difficulty_ends_the_streak() {
+        let mut state = ChimpTestState::with_seed(8);
+        open_input(&mut state, Duration::ZERO);
+        clear_board(&mut state);
+        assert_eq!(state.streak.current(), 1);
+        match state.toggle_hard() {
+            GameAction::Record(record, _) => assert_eq!(record.label, STREAK_LABEL),
+            _ => panic!("the streak so far is recorded"),
+        }
+        assert_eq!(state.streak.current(), 0);
+        assert_ne!(state.record_label(), "Level");
+    }
+
+    #[test]
+    fn learning_mode_keeps_numbers_visible_and_records_nothing() {
+        let mut state = ChimpTestState::with_seed(5);
+        state.toggle_learning();
+        open_input(&mut state, Duration::ZERO);
+        let first = state.tiles.iter().find(|tile| tile.value == 1).unwrap().pos;
+        state.cursor = first;
+        state.select();
+        assert!(!state.numbers_hidden);
+
+        // Clear the rest of the board.
+        let mut rest: Vec<_> = state
+            .tiles
+            .iter()
+            .filter(|tile| !tile.cleared)
+            .map(|tile| (tile.value, tile.pos))
+            .collect();
+        rest.sort();
+        for (_, pos) in rest {
+            state.cursor = pos;
+            assert!(matches!(state.select(), GameAction::None));
+        }
+        assert_eq!(state.level, 2);
+        assert_eq!(state.best, 0);
+        assert!(matches!(state.end_session(), GameAction::None));
+    }
 }
@@ -1,16 +1,21 @@
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crossterm::event::Event;
 use ratatui::Frame;
 use ratatui::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::config::Config;
+
 pub mod aim;
 pub mod chimp_test;
 pub mod navigation;
 pub mod number_memory;
 pub mod reaction;
+pub mod rhythm;
 pub mod sequence;
+pub mod spatial_span;
+pub mod typing_accuracy;
 pub mod typing_game;
 pub mod verbal_memory;
 pub mod visual_memory;
@@ -25,10 +30,13 @@ pub enum GameKind {
     ChimpTest,
     VisualMemory,
     Typing,
+    TypingAccuracy,
+    SpatialSpan,
+    Rhythm,
 }
 
 impl GameKind {
-    pub const ALL: [GameKind; 8] = [
+    pub const ALL: [GameKind; 11] = [
         GameKind::Reaction,
         GameKind::Sequence,
         GameKind::AimTrainer,
@@ -37,6 +45,9 @@ impl GameKind {
         GameKind::ChimpTest,
         GameKind::VisualMemory,
         GameKind::Typing,
+        GameKind::TypingAccuracy,
+        GameKind::SpatialSpan,
+        GameKind::Rhythm,
     ];
 
     pub fn title(self) -> &'static str {
@@ -49,6 +60,9 @@ impl GameKind {
             GameKind::ChimpTest => "Chimp Test",
             GameKind::VisualMemory => "Visual Memory",
             GameKind::Typing => "Typing",
+            GameKind::TypingAccuracy => "Typing Accuracy",
+            GameKind::SpatialSpan => "Spatial Span",
+            GameKind::Rhythm => "Rhythm",
         }
     }
 
@@ -62,18 +76,157 @@ impl GameKind {
             GameKind::ChimpTest => "Select numbers in ascending order.",
             GameKind::VisualMemory => "Remember highlighted tiles.",
             GameKind::Typing => "Type the prompt as quickly as you can.",
+            GameKind::TypingAccuracy => "Type tricky strings cleanly; any slip starts over.",
+            GameKind::SpatialSpan => "Tap the flashed blocks back in the same order.",
+            GameKind::Rhythm => "Tap exactly on the beat of a steady metronome.",
+        }
+    }
+
+    /// The key a game's history is stored under in `scores.json`; matches the
+    /// serde variant name so older files keep loading.
+    pub fn persist_key(self) -> &'static str {
+        match self {
+            GameKind::Reaction => "Reaction",
+            GameKind::Sequence => "Sequence",
+            GameKind::AimTrainer => "AimTrainer",
+            GameKind::NumberMemory => "NumberMemory",
+            GameKind::VerbalMemory => "VerbalMemory",
+            GameKind::ChimpTest => "ChimpTest",
+            GameKind::VisualMemory => "VisualMemory",
+            GameKind::Typing => "Typing",
+            GameKind::TypingAccuracy => "TypingAccuracy",
+            GameKind::SpatialSpan => "SpatialSpan",
+            GameKind::Rhythm => "Rhythm",
+        }
+    }
+
+    pub fn from_persist_key(key: &str) -> Option<GameKind> {
+        GameKind::ALL
+            .into_iter()
+            .find(|kind| kind.persist_key() == key)
+    }
+
+    /// `AimTrainer` -> `aim-trainer`.
+    pub fn slug(self) -> String {
+        let mut slug = String::new();
+        for ch in self.persist_key().chars() {
+            if ch.is_ascii_uppercase() && !slug.is_empty() {
+                slug.push('-');
+            }
+            slug.push(ch.to_ascii_lowercase());
+        }
+        slug
+    }
+
+    /// The game with this slug, or the only one whose slug starts with it,
+    /// so `aim` finds `aim-trainer` while `typing` stays exact.
+    pub fn from_slug(input: &str) -> Option<GameKind> {
+        let input = input.to_ascii_lowercase();
+        if let Some(kind) = GameKind::ALL.into_iter().find(|kind| kind.slug() == input) {
+            return Some(kind);
+        }
+        let mut matches = GameKind::ALL
+            .into_iter()
+            .filter(|kind| kind.slug().starts_with(&input));
+        match (matches.next(), matches.next()) {
+            (Some(kind), None) => Some(kind),
+            _ => None,
         }
     }
 
     pub fn score_direction(self) -> ScoreDirection {
         match self {
-            GameKind::Reaction | GameKind::AimTrainer => ScoreDirection::LowerIsBetter,
+            GameKind::Reaction
+            | GameKind::AimTrainer
+            | GameKind::TypingAccuracy
+            | GameKind::Rhythm => ScoreDirection::LowerIsBetter,
             GameKind::Sequence
             | GameKind::NumberMemory
             | GameKind::VerbalMemory
             | GameKind::ChimpTest
             | GameKind::VisualMemory
-            | GameKind::Typing => ScoreDirection::HigherIsBetter,
+            | GameKind::Typing
+            | GameKind::SpatialSpan => ScoreDirection::HigherIsBetter,
+        }
+    }
+
+    pub fn new_state(self, config: &Config) -> Box<dyn Game> {
+        match self {
+            GameKind::Reaction => Box::new(reaction::ReactionState::new()),
+            GameKind::Sequence => Box::new(
+                sequence::SequenceState::new()
+                    .with_pace(config.sequence_pace)
+                    .with_tile_colors(!config.sequence_monochrome)
+                    .with_ready_delay(config.sequence_ready()),
+            ),
+            GameKind::AimTrainer => Box::new(
+                aim::AimTrainerState::new()
+                    .with_flash(config.aim_flash())
+                    .with_preset(config.aim_preset)
+                    .with_weighted(config.aim_weighted),
+            ),
+            GameKind::NumberMemory => Box::new(
+                number_memory::NumberMemoryState::new()
+                    .with_grouping(config.number_group, config.number_group_input)
+                    .with_metronome(config.number_metronome),
+            ),
+            GameKind::VerbalMemory => {
+                Box::new(verbal_memory::VerbalMemoryState::new().with_mode(config.verbal_mode()))
+            }
+            GameKind::ChimpTest => {
+                Box::new(chimp_test::ChimpTestState::new().with_hard(config.chimp_hard))
+            }
+            GameKind::VisualMemory => Box::new(
+                visual_memory::VisualMemoryState::new().with_reveal(config.visual_reveal()),
+            ),
+            GameKind::Typing => {
+                Box::new(typing_game::TypingState::new().with_round_duration(config.typing_round()))
+            }
+            GameKind::TypingAccuracy => Box::new(typing_accuracy::TypingAccuracyState::new()),
+            GameKind::SpatialSpan => Box::new(spatial_span::SpatialSpanState::new()),
+            GameKind::Rhythm => Box::new(rhythm::RhythmState::new()),
+        }
+    }
+
+    pub fn seeded_state(self, seed: u64, config: &Config) -> Box<dyn Game> {
+        match self {
+            GameKind::Reaction => Box::new(reaction::ReactionState::with_seed(seed)),
+            GameKind::Sequence => Box::new(
+                sequence::SequenceState::with_seed(seed)
+                    .with_pace(config.sequence_pace)
+                    .with_tile_colors(!config.sequence_monochrome)
+                    .with_ready_delay(config.sequence_ready()),
+            ),
+            GameKind::AimTrainer => Box::new(
+                aim::AimTrainerState::with_seed(seed)
+                    .with_flash(config.aim_flash())
+                    .with_preset(config.aim_preset)
+                    .with_weighted(config.aim_weighted),
+            ),
+            GameKind::NumberMemory => Box::new(
+                number_memory::NumberMemoryState::with_seed(seed)
+                    .with_grouping(config.number_group, config.number_group_input)
+                    .with_metronome(config.number_metronome),
+            ),
+            GameKind::VerbalMemory => Box::new(
+                verbal_memory::VerbalMemoryState::with_seed(seed).with_mode(config.verbal_mode()),
+            ),
+            GameKind::ChimpTest => {
+                Box::new(chimp_test::ChimpTestState::with_seed(seed).with_hard(config.chimp_hard))
+            }
+            GameKind::VisualMemory => Box::new(
+                visual_memory::VisualMemoryState::with_seed(seed)
+                    .with_reveal(config.visual_reveal()),
+            ),
+            GameKind::Typing => Box::new(
+                typing_game::TypingState::with_seed(seed)
+                    .with_round_duration(config.typing_round()),
+            ),
+            GameKind::TypingAccuracy => {
+                Box::new(typing_accuracy::TypingAccuracyState::with_seed(seed))
+            }
+            GameKind::SpatialSpan => Box::new(spatial_span::SpatialSpanState::with_seed(seed)),
+            GameKind::Rhythm => Box::new(rhythm::RhythmState::new()),
         }
     }
 }
@@ -90,6 +243,10 @@ pub struct StatRecord {
     pub value: String,
     pub score: f64,
     pub recorded_at: u64,
+    /// Free-form tag added with `:note`, e.g. "new keyboard", or detail a
+    /// game attached to the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl StatRecord {
@@ -103,104 +260,197 @@ impl StatRecord {
             value: value.into(),
             score,
             recorded_at,
+            note: None,
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub enum GameAction {
-    None,
-    Record(StatRecord, GameKind),
+    /// Detail kept beside the score, such as a secondary timing.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
 }
 
-#[derive(Debug)]
-pub enum GameState {
-    Reaction(reaction::ReactionState),
-    Sequence(sequence::SequenceState),
-    Aim(aim::AimTrainerState),
-    Number(number_memory::NumberMemoryState),
-    Verbal(verbal_memory::VerbalMemoryState),
-    Chimp(chimp_test::ChimpTestState),
-    Visual(visual_memory::VisualMemoryState),
-    Typing(typing_game::TypingState),
+/// Label of the records `Streak` saves. They sit in the same history as
+/// the game's runs but are a side stat, so the menu never follows them.
+pub const STREAK_LABEL: &str = "Streak";
+
+/// Consecutive successful rounds within one session, kept apart from the
+/// per-run best. Any failure resets the current run of successes.
+#[derive(Debug, Default)]
+pub struct Streak {
+    current: u32,
+    best: u32,
 }
 
-impl GameState {
-    pub fn new(kind: GameKind) -> Self {
-        match kind {
-            GameKind::Reaction => Self::Reaction(reaction::ReactionState::new()),
-            GameKind::Sequence => Self::Sequence(sequence::SequenceState::new()),
-            GameKind::AimTrainer => Self::Aim(aim::AimTrainerState::new()),
-            GameKind::NumberMemory => Self::Number(number_memory::NumberMemoryState::new()),
-            GameKind::VerbalMemory => Self::Verbal(verbal_memory::VerbalMemoryState::new()),
-            GameKind::ChimpTest => Self::Chimp(chimp_test::ChimpTestState::new()),
-            GameKind::VisualMemory => Self::Visual(visual_memory::VisualMemoryState::new()),
-            GameKind::Typing => Self::Typing(typing_game::TypingState::new()),
+impl Streak {
+    pub fn hit(&mut self) {
+        self.current += 1;
+        self.best = self.best.max(self.current);
+    }
+
+    pub fn miss(&mut self) {
+        self.current = 0;
+    }
+
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// The session's longest streak as a record, once; nothing if no round
+    /// was ever won.
+    pub fn take_record(&mut self, kind: GameKind) -> GameAction {
+        let best = std::mem::take(&mut self.best);
+        self.current = 0;
+        if best == 0 {
+            return GameAction::None;
         }
+        GameAction::Record(
+            StatRecord::new(STREAK_LABEL, best.to_string(), best as f64),
+            kind,
+        )
     }
+}
 
+/// A minimal resumable copy of an unfinished run, saved on quit and offered
+/// back through `:resume` on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Snapshot {
+    Typing(typing_game::TypingSnapshot),
+    ChimpTest(chimp_test::ChimpSnapshot),
+    Sequence(sequence::SequenceSnapshot),
+}
+
+impl Snapshot {
     pub fn kind(&self) -> GameKind {
         match self {
-            GameState::Reaction(_) => GameKind::Reaction,
-            GameState::Sequence(_) => GameKind::Sequence,
-            GameState::Aim(_) => GameKind::AimTrainer,
-            GameState::Number(_) => GameKind::NumberMemory,
-            GameState::Verbal(_) => GameKind::VerbalMemory,
-            GameState::Chimp(_) => GameKind::ChimpTest,
-            GameState::Visual(_) => GameKind::VisualMemory,
-            GameState::Typing(_) => GameKind::Typing,
+            Snapshot::Typing(_) => GameKind::Typing,
+            Snapshot::ChimpTest(_) => GameKind::ChimpTest,
+            Snapshot::Sequence(_) => GameKind::Sequence,
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    pub fn restore(self, config: &Config) -> Box<dyn Game> {
         match self {
-            GameState::Reaction(state) => state.render(frame, area),
-            GameState::Sequence(state) => state.render(frame, area),
-            GameState::Aim(state) => state.render(frame, area),
-            GameState::Number(state) => state.render(frame, area),
-            GameState::Verbal(state) => state.render(frame, area),
-            GameState::Chimp(state) => state.render(frame, area),
-            GameState::Visual(state) => state.render(frame, area),
-            GameState::Typing(state) => state.render(frame, area),
+            Snapshot::Typing(snapshot) => Box::new(typing_game::TypingState::restore(snapshot)),
+            Snapshot::ChimpTest(snapshot) => {
+                Box::new(chimp_test::ChimpTestState::restore(snapshot))
+            }
+            Snapshot::Sequence(snapshot) => Box::new(
+                sequence::SequenceState::restore(snapshot)
+                    .with_pace(config.sequence_pace)
+                    .with_tile_colors(!config.sequence_monochrome)
+                    .with_ready_delay(config.sequence_ready()),
+            ),
         }
     }
+}
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
-        match self {
-            GameState::Reaction(state) => state.handle_event(event),
-            GameState::Sequence(state) => state.handle_event(event),
-            GameState::Aim(state) => state.handle_event(event),
-            GameState::Number(state) => state.handle_event(event),
-            GameState::Verbal(state) => state.handle_event(event),
-            GameState::Chimp(state) => state.handle_event(event),
-            GameState::Visual(state) => state.handle_event(event),
-            GameState::Typing(state) => state.handle_event(event),
+#[derive(Debug, Clone)]
+pub enum GameAction {
+    None,
+    Record(StatRecord, GameKind),
+    Bell,
+    /// An optional audible cue for a key moment (GO, round over, a wrong
+    /// answer). Unlike `Bell` it only rings when sound is enabled.
+    Cue,
+}
+
+pub trait Game {
+    fn kind(&self) -> GameKind;
+
+    /// The seed this run was built from, for games that use randomness.
+    /// `:replay` feeds it back through `GameKind::seeded_state`.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect);
+    fn handle_event(&mut self, event: &Event) -> GameAction;
+    fn handle_tick(&mut self, now: Instant) -> GameAction;
+    fn status_line(&self) -> String;
+
+    /// Whether the next tick may change what is on screen (timers, flashes,
+    /// pending phase changes). The app skips redraws while this is false, so
+    /// the conservative default is to always redraw.
+    fn is_animating(&self) -> bool {
+        true
+    }
+
+    /// How often this game wants `handle_tick`, or `None` for the app's
+    /// configured rate. Only wake-ups change; scoring stays `Instant`-based.
+    fn desired_tick(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Moves the game's clock anchors forward by `paused`, so time spent
+    /// paused is not charged to the player.
+    fn shift_clock(&mut self, _paused: Duration) {}
+
+    /// A resumable copy of the run in progress, or `None` when there is
+    /// nothing worth resuming.
+    fn snapshot(&self) -> Option<Snapshot> {
+        None
+    }
+
+    /// Called with the new terminal size so games that cache layout-derived
+    /// data can recompute it before the next draw.
+    fn on_resize(&mut self, _width: u16, _height: u16) {}
+
+    /// Starts a new run if the current one is over and returns true. Backs
+    /// the shared `r` restart key; while a run is in progress it returns
+    /// false and the key reaches `handle_event` as usual.
+    fn restart_finished(&mut self) -> bool {
+        false
+    }
+
+    /// Called once when the game is closed, whether for the menu, a restart
+    /// or quitting, so session-wide stats such as streaks can be recorded.
+    fn end_session(&mut self) -> GameAction {
+        GameAction::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streak_records_the_longest_run_of_successes() {
+        let mut streak = Streak::default();
+        streak.hit();
+        streak.hit();
+        streak.hit();
+        streak.miss();
+        streak.hit();
+        assert_eq!(streak.current(), 1);
+        match streak.take_record(GameKind::VisualMemory) {
+            GameAction::Record(record, GameKind::VisualMemory) => {
+                assert_eq!((record.label.as_str(), record.score), (STREAK_LABEL, 3.0));
+            }
+            _ => panic!("a streak was won"),
         }
+        assert!(matches!(
+            streak.take_record(GameKind::VisualMemory),
+            GameAction::None
+        ));
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        match self {
-            GameState::Reaction(state) => state.handle_tick(now),
-            GameState::Sequence(state) => state.handle_tick(now),
-            GameState::Aim(state) => state.handle_tick(now),
-            GameState::Number(state) => state.handle_tick(now),
-            GameState::Verbal(state) => state.handle_tick(now),
-            GameState::Chimp(state) => state.handle_tick(now),
-            GameState::Visual(state) => state.handle_tick(now),
-            GameState::Typing(state) => state.handle_tick(now),
+    #[test]
+    fn seeded_games_report_their_seed() {
+        let config = Config::default();
+        for kind in GameKind::ALL {
+            let expected = (kind != GameKind::Rhythm).then_some(123);
+            assert_eq!(kind.seeded_state(123, &config).seed(), expected, "{kind:?}");
         }
     }
 
-    pub fn status_line(&self) -> String {
-        match self {
-            GameState::Reaction(state) => state.status_line(),
-            GameState::Sequence(state) => state.status_line(),
-            GameState::Aim(state) => state.status_line(),
-            GameState::Number(state) => state.status_line(),
-            GameState::Verbal(state) => state.status_line(),
-            GameState::Chimp(state) => state.status_line(),
-            GameState::Visual(state) => state.status_line(),
-            GameState::Typing(state) => state.status_line(),
+    #[test]
+    fn every_kind_builds_a_game_of_that_kind() {
+        let config = Config::default();
+        for kind in GameKind::ALL {
+            assert_eq!(kind.new_state(&config).kind(), kind);
+            assert_eq!(kind.seeded_state(7, &config).kind(), kind);
         }
     }
 }
@@ -1,5 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
+use crate::keymap::{self, Action};
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct VimMotionState {
     count: Option<usize>,
@@ -22,7 +24,13 @@ impl VimMotionState {
         cursor: &mut (usize, usize),
         width: usize,
         height: usize,
+        occupied: Option<&dyn Fn(usize, usize) -> bool>,
     ) -> bool {
+        if let Some((dx, dy)) = motion_delta(key.code) {
+            self.consume_pending();
+            self.move_cursor(cursor, width, height, dx, dy);
+            return true;
+        }
         match key.code {
             KeyCode::Char(ch) if ch.is_ascii_digit() => {
                 self.pending_g = false;
@@ -56,10 +64,13 @@ impl VimMotionState {
             }
             KeyCode::Char('G') => {
                 self.pending_g = false;
-                self.count = None;
                 if height > 0 {
-                    cursor.1 = height - 1;
+                    cursor.1 = match self.count.take() {
+                        Some(row) => row.clamp(1, height) - 1,
+                        None => height - 1,
+                    };
                 }
+                self.count = None;
                 true
             }
             KeyCode::Char('$') => {
@@ -70,24 +81,14 @@ impl VimMotionState {
                 self.count = None;
                 true
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                self.consume_pending();
-                self.move_cursor(cursor, width, height, -1, 0);
-                true
-            }
-            KeyCode::Right | KeyCode::Char('l') => {
+            KeyCode::Char('w') => {
                 self.consume_pending();
-                self.move_cursor(cursor, width, height, 1, 0);
+                self.jump_word(cursor, width, height, occupied, true);
                 true
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            KeyCode::Char('b') => {
                 self.consume_pending();
-                self.move_cursor(cursor, width, height, 0, -1);
-                true
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.consume_pending();
-                self.move_cursor(cursor, width, height, 0, 1);
+                self.jump_word(cursor, width, height, occupied, false);
                 true
             }
             _ => {
@@ -123,7 +124,131 @@ impl VimMotionState {
         }
     }
 
+    /// Jumps along the cursor's row to the next (or previous) occupied cell,
+    /// or to the row's end (or start) when the game has no notion of occupancy.
+    fn jump_word(
+        &mut self,
+        cursor: &mut (usize, usize),
+        width: usize,
+        height: usize,
+        occupied: Option<&dyn Fn(usize, usize) -> bool>,
+        forward: bool,
+    ) {
+        let steps = self.count.take().unwrap_or(1);
+        if width == 0 || height == 0 {
+            return;
+        }
+        let Some(occupied) = occupied else {
+            cursor.0 = if forward { width - 1 } else { 0 };
+            return;
+        };
+        for _ in 0..steps {
+            let next = if forward {
+                (cursor.0 + 1..width).find(|&x| occupied(x, cursor.1))
+            } else {
+                (0..cursor.0).rev().find(|&x| occupied(x, cursor.1))
+            };
+            match next {
+                Some(x) => cursor.0 = x,
+                None => break,
+            }
+        }
+    }
+
     fn consume_pending(&mut self) {
         self.pending_g = false;
     }
 }
+
+fn motion_delta(code: KeyCode) -> Option<(isize, isize)> {
+    let keymap = keymap::current();
+    if keymap.is(code, Action::MoveLeft) {
+        Some((-1, 0))
+    } else if keymap.is(code, Action::MoveRight) {
+        Some((1, 0))
+    } else if keymap.is(code, Action::MoveUp) {
+        Some((0, -1))
+    } else if keymap.is(code, Action::MoveDown) {
+        Some((0, 1))
+    } else {
+        None
+    }
+}
+
+/// Relative line numbers for a grid's left gutter, one entry per row, padded
+/// to a common width so the grid columns stay aligned.
+pub fn relative_gutter(cursor_row: usize, height: usize) -> Vec<String> {
+    let width = height.saturating_sub(1).max(1).to_string().len();
+    (0..height)
+        .map(|row| format!("{:>width$} ", row.abs_diff(cursor_row)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn w_skips_empty_columns_to_the_next_tile() {
+        let occupied = |x: usize, y: usize| y == 1 && (x == 4 || x == 6);
+        let mut nav = VimMotionState::default();
+        let mut cursor = (1, 1);
+        assert!(nav.handle_key(&key(KeyCode::Char('w')), &mut cursor, 8, 3, Some(&occupied)));
+        assert_eq!(cursor, (4, 1));
+        nav.handle_key(&key(KeyCode::Char('w')), &mut cursor, 8, 3, Some(&occupied));
+        assert_eq!(cursor, (6, 1));
+        // Nothing further along the row, so the cursor stays put.
+        nav.handle_key(&key(KeyCode::Char('w')), &mut cursor, 8, 3, Some(&occupied));
+        assert_eq!(cursor, (6, 1));
+        nav.handle_key(&key(KeyCode::Char('b')), &mut cursor, 8, 3, Some(&occupied));
+        assert_eq!(cursor, (4, 1));
+
+        nav.handle_key(&key(KeyCode::Char('w')), &mut cursor, 8, 3, None);
+        assert_eq!(cursor, (7, 1));
+    }
+
+    #[test]
+    fn gutter_counts_rows_away_from_the_cursor() {
+        assert_eq!(relative_gutter(2, 5), vec!["2 ", "1 ", "0 ", "1 ", "2 "]);
+        assert_eq!(relative_gutter(0, 12)[11], "11 ");
+        assert_eq!(relative_gutter(0, 12)[1], " 1 ");
+    }
+
+    fn press(nav: &mut VimMotionState, cursor: &mut (usize, usize), keys: &str) {
+        for ch in keys.chars() {
+            nav.handle_key(&key(KeyCode::Char(ch)), cursor, 10, 10, None);
+        }
+    }
+
+    #[test]
+    fn bare_g_jumps_to_the_last_row() {
+        let mut nav = VimMotionState::default();
+        let mut cursor = (3, 4);
+        press(&mut nav, &mut cursor, "G");
+        assert_eq!(cursor, (3, 9));
+    }
+
+    #[test]
+    fn counted_g_jumps_to_that_row() {
+        let mut nav = VimMotionState::default();
+        let mut cursor = (3, 4);
+        press(&mut nav, &mut cursor, "1G");
+        assert_eq!(cursor, (3, 0));
+        assert_eq!(nav.prefix(), None);
+    }
+
+    #[test]
+    fn counted_g_clamps_to_the_last_row() {
+        let mut nav = VimMotionState::default();
+        let mut cursor = (3, 4);
+        press(&mut nav, &mut cursor, "99G");
+        assert_eq!(cursor, (3, 9));
+        assert_eq!(nav.prefix(), None);
+    }
+}
@@ -1,84 +1,253 @@
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{Event, KeyCode};
 use rand::{Rng, SeedableRng, rngs::StdRng};
 use ratatui::prelude::*;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use super::{GameAction, GameKind, StatRecord};
+use super::{Game, GameAction, GameKind, StatRecord};
 
 const REVEAL_TIME: Duration = Duration::from_secs(2);
+const PEEK_TIME: Duration = Duration::from_millis(800);
 
 #[derive(Debug)]
 pub struct NumberMemoryState {
     round: usize,
-    best_round: usize,
+    base: Base,
+    best_rounds: HashMap<Base, usize>,
     number: String,
     input: String,
     phase: Phase,
     status: String,
+    /// The number was shown again during this round's recall.
+    peeked: bool,
+    /// Digits per group when the number is shown, 0 for none, and whether
+    /// the typed answer is grouped too. Only the display changes.
+    group: usize,
+    group_input: bool,
+    /// Metronome: a highlight steps through the digits over the reveal,
+    /// ticking on each one, so the number is read in rhythm.
+    metronome: bool,
+    /// Digit the metronome last ticked on this reveal.
+    beat: Option<usize>,
+    seed: u64,
     rng: StdRng,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Base {
+    Decimal,
+    Binary,
+    Hex,
+}
+
+impl Base {
+    fn radix(self) -> u32 {
+        match self {
+            Base::Decimal => 10,
+            Base::Binary => 2,
+            Base::Hex => 16,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Base::Decimal => Base::Binary,
+            Base::Binary => Base::Hex,
+            Base::Hex => Base::Decimal,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Base::Decimal => "decimal",
+            Base::Binary => "binary",
+            Base::Hex => "hex",
+        }
+    }
+
+    /// Each base is its own series in the menu. Decimal keeps the plain
+    /// label so runs from before bases existed stay in its series.
+    fn record_label(self) -> &'static str {
+        match self {
+            Base::Decimal => "Digits",
+            Base::Binary => "Digits(bin)",
+            Base::Hex => "Digits(hex)",
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Phase {
     Ready,
     Reveal { since: Instant },
     Recall,
+    Peek { since: Instant },
     Result,
 }
 
 impl NumberMemoryState {
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
         Self {
+            seed,
             round: 1,
-            best_round: 0,
+            base: Base::Decimal,
+            best_rounds: HashMap::new(),
             number: String::new(),
             input: String::new(),
             phase: Phase::Ready,
-            status: "Press enter to reveal the number".into(),
-            rng: StdRng::from_entropy(),
+            status: "Press enter to reveal the number · b to change base · m metronome".into(),
+            peeked: false,
+            group: 0,
+            group_input: false,
+            metronome: false,
+            beat: None,
+            rng,
+        }
+    }
+
+    pub fn with_grouping(mut self, group: usize, group_input: bool) -> Self {
+        self.group = group;
+        self.group_input = group_input;
+        self
+    }
+
+    pub fn with_metronome(mut self, metronome: bool) -> Self {
+        self.metronome = metronome;
+        self
+    }
+
+    fn best_round(&self) -> usize {
+        self.best_rounds.get(&self.base).copied().unwrap_or(0)
+    }
+
+    fn cycle_base(&mut self) {
+        self.base = self.base.next();
+        self.round = 1;
+        self.status = format!(
+            "Base: {} · press enter to reveal the number",
+            self.base.name()
+        );
+    }
+
+    fn toggle_metronome(&mut self) {
+        self.metronome = !self.metronome;
+        self.status = if self.metronome {
+            "Metronome on · digits light up one at a time".into()
+        } else {
+            "Metronome off".into()
+        };
+    }
+
+    /// The digit the metronome is on at `now`, while the number is first
+    /// shown.
+    fn beat_at(&self, now: Instant) -> Option<usize> {
+        match self.phase {
+            Phase::Reveal { since } if self.metronome => Some(beat_index(
+                now.saturating_duration_since(since),
+                REVEAL_TIME,
+                self.number.chars().count(),
+            )),
+            _ => None,
         }
     }
 
+    /// The number split into groups, with the metronome's digit picked out.
+    fn number_spans(&self, beat: Option<usize>) -> Vec<Span<'static>> {
+        let mut spans = vec![Span::raw("Number: ")];
+        for (index, ch) in self.number.chars().enumerate() {
+            if self.group > 0 && index > 0 && index % self.group == 0 {
+                spans.push(Span::raw(" "));
+            }
+            let style = if beat == Some(index) {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        spans
+    }
+
     fn build_number(&mut self) {
+        let radix = self.base.radix();
         self.number = (0..self.round)
-            .map(|_| char::from(b'0' + self.rng.gen_range(0..10) as u8))
+            .filter_map(|_| char::from_digit(self.rng.gen_range(0..radix), radix))
             .collect();
         self.phase = Phase::Reveal {
             since: Instant::now(),
         };
         self.status = format!("Memorize {} digits", self.round);
+        self.peeked = false;
+        self.beat = None;
+    }
+
+    /// Shows the number once more; a round that used it cannot set a best.
+    fn peek(&mut self) {
+        if self.peeked {
+            self.status = "Already peeked this round".into();
+            return;
+        }
+        self.peeked = true;
+        self.phase = Phase::Peek {
+            since: Instant::now(),
+        };
+        self.status = "Peeking · this round won't count toward your best".into();
     }
 
     fn handle_submission(&mut self) -> GameAction {
         if self.input == self.number {
-            self.status = "Correct!".into();
+            self.status = "✓ Correct!".into();
             self.round += 1;
             self.phase = Phase::Result;
             self.input.clear();
-            if self.round - 1 > self.best_round {
-                self.best_round = self.round - 1;
+            let completed = self.round - 1;
+            if self.peeked {
+                self.status = "✓ Correct! (peeked, not counted)".into();
+            } else if completed > self.best_round() {
+                self.best_rounds.insert(self.base, completed);
                 return GameAction::Record(
                     StatRecord::new(
-                        "Digits",
-                        self.best_round.to_string(),
-                        self.best_round as f64,
+                        self.base.record_label(),
+                        completed.to_string(),
+                        completed as f64,
                     ),
                     GameKind::NumberMemory,
                 );
             }
         } else {
-            self.status = format!("Oops! It was {}", self.number);
+            self.status = format!(
+                "✗ Oops! It was {} · r to restart",
+                group_digits(&self.number, self.group)
+            );
             self.round = 1;
             self.phase = Phase::Result;
             self.input.clear();
+            return GameAction::Cue;
         }
         GameAction::None
     }
+}
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+impl Game for NumberMemoryState {
+    fn kind(&self) -> GameKind {
+        GameKind::NumberMemory
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Number Memory")
             .borders(Borders::ALL)
@@ -86,31 +255,44 @@ impl NumberMemoryState {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        let mut lines = vec![Line::from(format!("Round: {} digits", self.round))];
-        lines.push(Line::from(format!("Best: {}", self.best_round)));
+        let mut lines = vec![Line::from(format!(
+            "Round: {} digits · {}",
+            self.round,
+            self.base.name()
+        ))];
+        lines.push(Line::from(format!("Best: {}", self.best_round())));
         match self.phase {
             Phase::Recall => {
-                lines.push(Line::from(format!("Type: {}", self.input)));
+                let group = if self.group_input { self.group } else { 0 };
+                lines.push(Line::from(format!(
+                    "Type: {}",
+                    group_digits(&self.input, group)
+                )));
+            }
+            Phase::Reveal { .. } | Phase::Peek { .. } => {
+                lines.push(Line::from(self.number_spans(self.beat_at(Instant::now()))))
             }
-            Phase::Reveal { .. } => lines.push(Line::from(format!("Number: {}", self.number))),
             _ => {}
         }
         lines.push(Line::from(self.status.as_str()));
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
             match (&self.phase, key.code) {
                 (Phase::Ready | Phase::Result, KeyCode::Enter) => {
                     self.build_number();
                 }
+                (Phase::Ready | Phase::Result, KeyCode::Char('b')) => self.cycle_base(),
+                (Phase::Ready | Phase::Result, KeyCode::Char('m')) => self.toggle_metronome(),
                 (Phase::Recall, KeyCode::Enter) => return self.handle_submission(),
+                (Phase::Recall, KeyCode::Char('?')) => self.peek(),
                 (Phase::Recall, KeyCode::Backspace) => {
                     self.input.pop();
                 }
-                (Phase::Recall, KeyCode::Char(ch)) if ch.is_ascii_digit() => {
-                    self.input.push(ch);
+                (Phase::Recall, KeyCode::Char(ch)) if ch.is_digit(self.base.radix()) => {
+                    self.input.push(ch.to_ascii_lowercase());
                 }
                 _ => {}
             }
@@ -118,17 +300,163 @@ impl NumberMemoryState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Phase::Reveal { since } = self.phase {
-            if now.duration_since(since) >= REVEAL_TIME {
-                self.phase = Phase::Recall;
-                self.status = "Type the number and press enter".into();
-            }
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        if let Phase::Reveal { since } = self.phase
+            && now.duration_since(since) >= REVEAL_TIME
+        {
+            self.phase = Phase::Recall;
+            self.status = "Type the number and press enter · ? to peek once".into();
+        }
+        if let Some(beat) = self.beat_at(now)
+            && self.beat != Some(beat)
+        {
+            self.beat = Some(beat);
+            return GameAction::Cue;
+        }
+        if let Phase::Peek { since } = self.phase
+            && now.duration_since(since) >= PEEK_TIME
+        {
+            self.phase = Phase::Recall;
+            self.status = "Type the number and press enter".into();
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
+    fn status_line(&self) -> String {
         self.status.clone()
     }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Phase::Reveal { since } | Phase::Peek { since } = &mut self.phase {
+            *since += paused;
+        }
+    }
+
+    /// Over means the last answer was wrong, which sends the round back to 1.
+    fn restart_finished(&mut self) -> bool {
+        if !matches!(self.phase, Phase::Result) || self.round != 1 {
+            return false;
+        }
+        self.build_number();
+        true
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Reveal { .. } | Phase::Peek { .. })
+    }
+}
+
+/// Which of `len` digits is lit `elapsed` into a reveal lasting `window`,
+/// each digit getting an equal slice.
+fn beat_index(elapsed: Duration, window: Duration, len: usize) -> usize {
+    if len == 0 || window.is_zero() {
+        return 0;
+    }
+    let index = elapsed.as_nanos() * len as u128 / window.as_nanos();
+    (index as usize).min(len - 1)
+}
+
+/// "4827193055" in groups of 3 is "482 719 305 5"; a size of 0 leaves the
+/// digits as they are.
+fn group_digits(digits: &str, size: usize) -> String {
+    if size == 0 {
+        return digits.to_string();
+    }
+    let chars: Vec<char> = digits.chars().collect();
+    chars
+        .chunks(size)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn hex_mode_generates_and_accepts_letters() {
+        let mut state = NumberMemoryState::with_seed(5);
+        state.cycle_base();
+        state.cycle_base();
+        assert_eq!(state.base, Base::Hex);
+        state.round = 40;
+        state.build_number();
+        assert!(state.number.chars().all(|ch| ch.is_ascii_hexdigit()));
+        assert!(state.number.chars().any(|ch| ('a'..='f').contains(&ch)));
+
+        state.phase = Phase::Recall;
+        for ch in state.number.clone().chars() {
+            state.handle_event(&key(KeyCode::Char(ch.to_ascii_uppercase())));
+        }
+        state.handle_event(&key(KeyCode::Char('g')));
+        assert_eq!(state.input, state.number);
+        match state.handle_event(&key(KeyCode::Enter)) {
+            GameAction::Record(record, _) => assert_eq!(record.label, "Digits(hex)"),
+            _ => panic!("a correct answer sets a best"),
+        }
+    }
+
+    #[test]
+    fn digits_are_grouped_for_display() {
+        assert_eq!(group_digits("1234567", 3), "123 456 7");
+        assert_eq!(group_digits("123456", 3), "123 456");
+        assert_eq!(group_digits("1234567", 0), "1234567");
+        assert_eq!(group_digits("", 3), "");
+    }
+
+    #[test]
+    fn metronome_is_halfway_through_the_digits_at_mid_reveal() {
+        assert_eq!(beat_index(REVEAL_TIME / 2, REVEAL_TIME, 10), 5);
+        assert_eq!(beat_index(REVEAL_TIME / 2, REVEAL_TIME, 7), 3);
+        assert_eq!(beat_index(REVEAL_TIME * 3, REVEAL_TIME, 7), 6);
+        assert_eq!(beat_index(REVEAL_TIME, REVEAL_TIME, 0), 0);
+
+        let mut state = NumberMemoryState::with_seed(1).with_metronome(true);
+        state.round = 10;
+        state.build_number();
+        let Phase::Reveal { since } = state.phase else {
+            panic!("the number is being shown");
+        };
+        assert!(matches!(
+            state.handle_tick(since + REVEAL_TIME / 2),
+            GameAction::Cue
+        ));
+        assert_eq!(state.beat, Some(5));
+        // One tick per digit, however often the app ticks.
+        assert!(matches!(
+            state.handle_tick(since + REVEAL_TIME / 2),
+            GameAction::None
+        ));
+    }
+
+    #[test]
+    fn only_one_peek_per_round() {
+        let mut state = NumberMemoryState::with_seed(2);
+        state.phase = Phase::Recall;
+        state.handle_event(&key(KeyCode::Char('?')));
+        let Phase::Peek { since } = state.phase else {
+            panic!("the first peek shows the number");
+        };
+        state.handle_tick(since + PEEK_TIME);
+        assert!(matches!(state.phase, Phase::Recall));
+
+        state.handle_event(&key(KeyCode::Char('?')));
+        assert!(matches!(state.phase, Phase::Recall));
+        assert_eq!(state.status, "Already peeked this round");
+
+        for ch in state.number.clone().chars() {
+            state.handle_event(&key(KeyCode::Char(ch)));
+        }
+        assert!(matches!(
+            state.handle_event(&key(KeyCode::Enter)),
+            GameAction::None
+        ));
+    }
 }
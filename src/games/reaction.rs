@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{Event, KeyCode};
@@ -6,17 +7,30 @@ use ratatui::prelude::*;
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use super::{GameAction, GameKind, StatRecord};
+use super::{Game, GameAction, GameKind, StatRecord};
+use crate::keymap::{self, Action};
+use crate::theme;
 
 const MIN_DELAY_MS: u64 = 1200;
 const MAX_DELAY_MS: u64 = 3200;
+const IDLE_TICK: Duration = Duration::from_millis(250);
+/// Faster than any human tap; a result below this is a double-fire.
+const MIN_PLAUSIBLE: Duration = Duration::from_millis(5);
+/// Slower than any attentive tap; a result above this means the player left.
+const MAX_PLAUSIBLE: Duration = Duration::from_secs(5);
+const BUCKET_MS: u128 = 25;
+const HISTOGRAM_ROWS: usize = 8;
+const HISTOGRAM_WIDTH: usize = 20;
 
 #[derive(Debug)]
 pub struct ReactionState {
     phase: Phase,
+    seed: u64,
     rng: StdRng,
     last_result: Option<u128>,
     best_ms: Option<u128>,
+    /// Every valid result this session, for the histogram.
+    results: Vec<u128>,
     status: String,
 }
 
@@ -30,11 +44,18 @@ enum Phase {
 
 impl ReactionState {
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
         Self {
+            seed,
             phase: Phase::Idle,
-            rng: StdRng::from_entropy(),
+            rng,
             last_result: None,
             best_ms: None,
+            results: Vec::new(),
             status: "Press enter to start".into(),
         }
     }
@@ -48,25 +69,57 @@ impl ReactionState {
         self.status = "Wait for GO...".into();
     }
 
+    /// Up to `HISTOGRAM_ROWS` bars, one per non-empty 25 ms bucket, fastest
+    /// first; further buckets are left off the bottom.
+    fn histogram_lines(&self) -> Vec<Line<'static>> {
+        let buckets = bucket_counts(&self.results);
+        let most = buckets.values().copied().max().unwrap_or(0);
+        let mut lines = vec![Line::from(format!(
+            "This session · {} attempts · c clears",
+            self.results.len()
+        ))];
+        for (&start, &count) in buckets.iter().take(HISTOGRAM_ROWS) {
+            let width = (count * HISTOGRAM_WIDTH).div_ceil(most);
+            lines.push(Line::from(vec![
+                Span::raw(format!("{:>4}–{:<4} ", start, start + BUCKET_MS - 1)),
+                Span::styled(
+                    theme::glyph("█", "#").repeat(width),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(format!(" {count}")),
+            ]));
+        }
+        lines
+    }
+
     fn action_key(code: &KeyCode) -> bool {
-        matches!(
-            code,
-            KeyCode::Enter
-                | KeyCode::Char(' ')
-                | KeyCode::Char('h')
-                | KeyCode::Char('j')
-                | KeyCode::Char('k')
-                | KeyCode::Char('l')
-        )
+        let keymap = keymap::current();
+        [
+            Action::Select,
+            Action::MoveLeft,
+            Action::MoveDown,
+            Action::MoveUp,
+            Action::MoveRight,
+        ]
+        .into_iter()
+        .any(|action| keymap.is(*code, action))
     }
 
     fn finish_attempt(&mut self, elapsed: Option<Duration>) -> Option<GameAction> {
         self.phase = Phase::Result;
         match elapsed {
+            Some(duration) if !(MIN_PLAUSIBLE..=MAX_PLAUSIBLE).contains(&duration) => {
+                self.last_result = None;
+                self.status = format!(
+                    "{} ms discarded as implausible · enter or r to retry",
+                    duration.as_millis()
+                );
+            }
             Some(duration) => {
                 let ms = duration.as_millis();
                 self.last_result = Some(ms);
-                self.status = format!("{ms} ms · press enter to retry");
+                self.results.push(ms);
+                self.status = format!("{ms} ms · enter or r to retry");
                 if self.best_ms.map(|best| ms < best).unwrap_or(true) {
                     self.best_ms = Some(ms);
                     return Some(GameAction::Record(
@@ -77,13 +130,23 @@ impl ReactionState {
             }
             None => {
                 self.last_result = None;
-                self.status = "Too soon! press enter to restart".into();
+                self.status = "Too soon! enter or r to restart".into();
             }
         }
         None
     }
+}
+
+impl Game for ReactionState {
+    fn kind(&self) -> GameKind {
+        GameKind::Reaction
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let mut lines = vec![Line::from("Reaction Time")];
         match self.phase {
             Phase::Go { .. } => lines.push(Line::from("GO!")),
@@ -97,6 +160,10 @@ impl ReactionState {
             lines.push(Line::from(format!("Session best: {best} ms")));
         }
         lines.push(Line::from(self.status.as_str()));
+        if matches!(self.phase, Phase::Idle | Phase::Result) && !self.results.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(self.histogram_lines());
+        }
 
         let block = Block::default()
             .title("Reaction Time")
@@ -104,17 +171,31 @@ impl ReactionState {
             .border_style(Style::default().fg(Color::Gray));
         let inner = block.inner(area);
         frame.render_widget(block, area);
-        frame.render_widget(Paragraph::new(lines), inner);
+        // Fill the whole area so GO is seen at a glance rather than found
+        // among the lines of text.
+        let style = match self.phase {
+            Phase::Go { .. } => Style::default()
+                .bg(Color::Green)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+            Phase::Waiting { .. } => Style::default().bg(Color::Indexed(236)).fg(Color::White),
+            _ => Style::default(),
+        };
+        frame.render_widget(Paragraph::new(lines).style(style), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
+            if key.code == KeyCode::Char('c') && matches!(self.phase, Phase::Idle | Phase::Result) {
+                self.results.clear();
+                return GameAction::None;
+            }
             if !Self::action_key(&key.code) {
                 return GameAction::None;
             }
             match self.phase {
                 Phase::Idle | Phase::Result => {
-                    if matches!(key.code, KeyCode::Enter | KeyCode::Char(' ')) {
+                    if keymap::current().is(key.code, Action::Select) {
                         self.start_wait();
                     }
                 }
@@ -133,17 +214,114 @@ impl ReactionState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Phase::Waiting { start, delay } = self.phase {
-            if now.duration_since(start) >= delay {
-                self.phase = Phase::Go { start: now };
-                self.status = "Tap now!".into();
-            }
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        if let Phase::Waiting { start, delay } = self.phase
+            && now.duration_since(start) >= delay
+        {
+            self.phase = Phase::Go { start: now };
+            self.status = "Tap now!".into();
+            return GameAction::Cue;
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
+    fn shift_clock(&mut self, paused: Duration) {
+        match &mut self.phase {
+            Phase::Waiting { start, .. } | Phase::Go { start } => *start += paused,
+            Phase::Idle | Phase::Result => {}
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !matches!(self.phase, Phase::Result) {
+            return false;
+        }
+        self.start_wait();
+        true
+    }
+
+    fn status_line(&self) -> String {
         self.status.clone()
     }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Waiting { .. } | Phase::Go { .. })
+    }
+
+    fn desired_tick(&self) -> Option<Duration> {
+        matches!(self.phase, Phase::Idle | Phase::Result).then_some(IDLE_TICK)
+    }
+}
+
+/// Results counted per 25 ms bucket, keyed by the bucket's first ms.
+fn bucket_counts(results: &[u128]) -> BTreeMap<u128, usize> {
+    let mut buckets = BTreeMap::new();
+    for ms in results {
+        *buckets.entry(ms / BUCKET_MS * BUCKET_MS).or_insert(0) += 1;
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn implausibly_fast_results_are_not_recorded() {
+        let mut state = ReactionState::with_seed(1);
+        assert!(
+            state
+                .finish_attempt(Some(Duration::from_millis(2)))
+                .is_none()
+        );
+        assert!(state.results.is_empty());
+        assert_eq!(state.best_ms, None);
+        assert!(state.status.contains("discarded"));
+
+        assert!(state.finish_attempt(Some(Duration::from_secs(6))).is_none());
+        assert!(matches!(
+            state.finish_attempt(Some(Duration::from_millis(180))),
+            Some(GameAction::Record(..))
+        ));
+        assert_eq!(state.results, [180]);
+    }
+
+    #[test]
+    fn go_asks_for_a_cue() {
+        let mut state = ReactionState::with_seed(4);
+        state.start_wait();
+        let Phase::Waiting { start, delay } = state.phase else {
+            panic!("waiting for GO");
+        };
+        assert!(matches!(state.handle_tick(start), GameAction::None));
+        assert!(matches!(state.handle_tick(start + delay), GameAction::Cue));
+        assert!(matches!(state.phase, Phase::Go { .. }));
+        assert!(matches!(state.handle_tick(start + delay), GameAction::None));
+    }
+
+    #[test]
+    fn go_fills_the_area_with_the_go_color() {
+        let mut state = ReactionState::with_seed(4);
+        let background = |state: &ReactionState| {
+            let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(30, 10)).unwrap();
+            terminal
+                .draw(|frame| state.render(frame, frame.size()))
+                .unwrap();
+            terminal.backend().buffer().get(15, 7).bg
+        };
+        assert_eq!(background(&state), Color::Reset);
+        state.start_wait();
+        assert_eq!(background(&state), Color::Indexed(236));
+        state.phase = Phase::Go {
+            start: Instant::now(),
+        };
+        assert_eq!(background(&state), Color::Green);
+    }
+
+    #[test]
+    fn histogram_bins_results_into_25ms_buckets() {
+        let buckets = bucket_counts(&[200, 210, 224, 225, 260]);
+        let expected: BTreeMap<u128, usize> = [(200, 3), (225, 1), (250, 1)].into();
+        assert_eq!(buckets, expected);
+    }
 }
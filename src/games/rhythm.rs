@@ -0,0 +1,237 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode};
+use ratatui::prelude::*;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use super::{Game, GameAction, GameKind, StatRecord};
+
+const BEAT_INTERVAL: Duration = Duration::from_millis(600);
+const LEAD_IN: Duration = Duration::from_millis(1200);
+const BEATS: usize = 16;
+const PULSE: Duration = Duration::from_millis(120);
+const BEAT_TICK: Duration = Duration::from_millis(10);
+
+#[derive(Debug)]
+pub struct RhythmState {
+    phase: Phase,
+    offsets: Vec<Option<i64>>,
+    next_beat: usize,
+    last_offset: Option<i64>,
+    best_ms: Option<f64>,
+    status: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Idle,
+    Running { start: Instant },
+    Result,
+}
+
+impl RhythmState {
+    pub fn new() -> Self {
+        Self {
+            phase: Phase::Idle,
+            offsets: vec![None; BEATS],
+            next_beat: 0,
+            last_offset: None,
+            best_ms: None,
+            status: "Press enter to start the beat".into(),
+        }
+    }
+
+    fn start(&mut self, now: Instant) {
+        self.phase = Phase::Running { start: now };
+        self.offsets = vec![None; BEATS];
+        self.next_beat = 0;
+        self.last_offset = None;
+        self.status = "Tap space on every beat".into();
+    }
+
+    fn beat_at(start: Instant, beat: usize) -> Instant {
+        start + LEAD_IN + BEAT_INTERVAL * beat as u32
+    }
+
+    fn register_press(&mut self, at: Instant) {
+        let Phase::Running { start } = self.phase else {
+            return;
+        };
+        let first = Self::beat_at(start, 0);
+        let since_first = at.saturating_duration_since(first).as_millis() as i64
+            - first.saturating_duration_since(at).as_millis() as i64;
+        let interval = BEAT_INTERVAL.as_millis() as i64;
+        let beat = ((since_first + interval / 2).div_euclid(interval)).clamp(0, BEATS as i64 - 1);
+        let offset = since_first - beat * interval;
+        let slot = &mut self.offsets[beat as usize];
+        if slot.is_none() {
+            *slot = Some(offset);
+            self.last_offset = Some(offset);
+        }
+    }
+
+    fn mean_error(&self) -> f64 {
+        let penalty = BEAT_INTERVAL.as_millis() as f64 / 2.0;
+        let total: f64 = self
+            .offsets
+            .iter()
+            .map(|offset| offset.map(|ms| ms.abs() as f64).unwrap_or(penalty))
+            .sum();
+        total / BEATS as f64
+    }
+
+    fn finish(&mut self) -> GameAction {
+        self.phase = Phase::Result;
+        let mean = self.mean_error();
+        let hits = self.offsets.iter().filter(|o| o.is_some()).count();
+        self.status =
+            format!("Mean error {mean:.0} ms · {hits}/{BEATS} beats hit · enter or r to retry");
+        if self.best_ms.map(|best| mean < best).unwrap_or(true) {
+            self.best_ms = Some(mean);
+            return GameAction::Record(
+                StatRecord::new("Timing", format!("{mean:.0} ms"), mean),
+                GameKind::Rhythm,
+            );
+        }
+        GameAction::None
+    }
+
+    fn pulse_visible(&self, now: Instant) -> bool {
+        let Phase::Running { start } = self.phase else {
+            return false;
+        };
+        self.next_beat > 0
+            && now.saturating_duration_since(Self::beat_at(start, self.next_beat - 1)) < PULSE
+    }
+}
+
+impl Game for RhythmState {
+    fn kind(&self) -> GameKind {
+        GameKind::Rhythm
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("Rhythm")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines = vec![Line::from(format!(
+            "Beat {}/{} · {} ms interval",
+            self.next_beat.min(BEATS),
+            BEATS,
+            BEAT_INTERVAL.as_millis()
+        ))];
+        let pulse = if self.pulse_visible(Instant::now()) {
+            Span::styled(
+                " ● ",
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled(" ○ ", Style::default().fg(Color::DarkGray))
+        };
+        lines.push(Line::from(pulse));
+        if let Some(offset) = self.last_offset {
+            lines.push(Line::from(format!("Last tap {}", format_offset(offset))));
+        }
+        if let Some(best) = self.best_ms {
+            lines.push(Line::from(format!("Session best: {best:.0} ms")));
+        }
+        lines.push(Line::from(self.status.as_str()));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> GameAction {
+        if let Event::Key(key) = event {
+            match (self.phase, key.code) {
+                (Phase::Idle | Phase::Result, KeyCode::Enter) => self.start(Instant::now()),
+                (Phase::Running { .. }, KeyCode::Char(' ') | KeyCode::Enter) => {
+                    self.register_press(Instant::now());
+                }
+                _ => {}
+            }
+        }
+        GameAction::None
+    }
+
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        let Phase::Running { start } = self.phase else {
+            return GameAction::None;
+        };
+        if self.next_beat < BEATS && now >= Self::beat_at(start, self.next_beat) {
+            self.next_beat += 1;
+            return GameAction::Bell;
+        }
+        if self.next_beat >= BEATS && now >= Self::beat_at(start, BEATS - 1) + BEAT_INTERVAL / 2 {
+            return self.finish();
+        }
+        GameAction::None
+    }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Phase::Running { start } = &mut self.phase {
+            *start += paused;
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !matches!(self.phase, Phase::Result) {
+            return false;
+        }
+        self.start(Instant::now());
+        true
+    }
+
+    fn status_line(&self) -> String {
+        match (self.phase, self.last_offset) {
+            (Phase::Running { .. }, Some(offset)) => format!(
+                "Beat {}/{} · last {}",
+                self.next_beat.min(BEATS),
+                BEATS,
+                format_offset(offset)
+            ),
+            _ => self.status.clone(),
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Running { .. })
+    }
+
+    /// Ticks fast while running so the bell lands close to each beat.
+    fn desired_tick(&self) -> Option<Duration> {
+        matches!(self.phase, Phase::Running { .. }).then_some(BEAT_TICK)
+    }
+}
+
+fn format_offset(offset: i64) -> String {
+    if offset >= 0 {
+        format!("+{offset}ms")
+    } else {
+        format!("{offset}ms")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_press_after_a_beat_registers_its_offset() {
+        let mut state = RhythmState::new();
+        let start = Instant::now();
+        state.start(start);
+        state.register_press(RhythmState::beat_at(start, 2) + Duration::from_millis(30));
+        assert_eq!(state.offsets[2], Some(30));
+        assert_eq!(state.last_offset, Some(30));
+
+        state.register_press(RhythmState::beat_at(start, 3) - Duration::from_millis(45));
+        assert_eq!(state.offsets[3], Some(-45));
+    }
+}
@@ -5,12 +5,67 @@ use rand::{Rng, SeedableRng, rngs::StdRng};
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
+use serde::{Deserialize, Serialize};
 
-use super::{GameAction, GameKind, StatRecord};
+use super::{Game, GameAction, GameKind, Snapshot, StatRecord};
+use crate::theme;
 
 const GRID: usize = 3;
 const FLASH_ON: Duration = Duration::from_millis(450);
 const FLASH_OFF: Duration = Duration::from_millis(180);
+const FLASH_ON_FLOOR: Duration = Duration::from_millis(200);
+const FLASH_OFF_FLOOR: Duration = Duration::from_millis(80);
+const READY_DELAY: Duration = Duration::from_millis(700);
+/// One color per grid position, row by row, Simon-style.
+const TILE_COLORS: [Color; GRID * GRID] = [
+    Color::Red,
+    Color::Green,
+    Color::Blue,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightBlue,
+];
+
+/// How quickly the flashes speed up as the pattern grows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SequencePace {
+    /// Fixed timing regardless of length.
+    Steady,
+    Normal,
+    Fast,
+}
+
+impl SequencePace {
+    /// Fraction shaved off both flash durations per tile past the first.
+    fn shrink_per_tile(self) -> f64 {
+        match self {
+            SequencePace::Steady => 0.0,
+            SequencePace::Normal => 0.06,
+            SequencePace::Fast => 0.12,
+        }
+    }
+
+    /// On/off durations for a pattern of `len` tiles, never below the floors.
+    fn flash_timing(self, len: usize) -> (Duration, Duration) {
+        let factor = (1.0 - self.shrink_per_tile()).powi(len.saturating_sub(1) as i32);
+        (
+            FLASH_ON.mul_f64(factor).max(FLASH_ON_FLOOR),
+            FLASH_OFF.mul_f64(factor).max(FLASH_OFF_FLOOR),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceSnapshot {
+    seed: u64,
+    sequence: Vec<(usize, usize)>,
+    best: usize,
+    pending_best: Option<usize>,
+}
 
 #[derive(Debug)]
 pub struct SequenceState {
@@ -19,13 +74,21 @@ pub struct SequenceState {
     idx: usize,
     best: usize,
     pending_best: Option<usize>,
+    seed: u64,
     rng: StdRng,
     phase: Phase,
     status: String,
+    pace: SequencePace,
+    tile_colors: bool,
+    /// Pause before each new round's pattern is shown.
+    ready_delay: Duration,
 }
 
 #[derive(Debug, Clone, Copy)]
 enum Phase {
+    Ready {
+        until: Instant,
+    },
     Showing {
         step: usize,
         visible: bool,
@@ -36,9 +99,14 @@ enum Phase {
 
 impl SequenceState {
     pub fn new() -> Self {
-        let mut rng = StdRng::from_entropy();
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
         let seq = vec![random_cell(&mut rng)];
         Self {
+            seed,
             sequence: seq,
             cursor: (0, 0),
             idx: 0,
@@ -51,6 +119,65 @@ impl SequenceState {
                 since: Instant::now(),
             },
             status: "Watch the pattern".into(),
+            pace: SequencePace::Normal,
+            tile_colors: true,
+            ready_delay: READY_DELAY,
+        }
+    }
+
+    /// Replays the saved pattern from the start. Later tiles come from a
+    /// fresh generator seeded with the original seed.
+    pub fn restore(snapshot: SequenceSnapshot) -> Self {
+        let mut state = Self::with_seed(snapshot.seed);
+        state.sequence = snapshot.sequence;
+        state.best = snapshot.best;
+        state.pending_best = snapshot.pending_best;
+        state.begin_new_round(false);
+        state
+    }
+
+    pub fn with_pace(mut self, pace: SequencePace) -> Self {
+        self.pace = pace;
+        self
+    }
+
+    /// `false` keeps every tile the same color for minimal terminals.
+    pub fn with_tile_colors(mut self, enabled: bool) -> Self {
+        self.tile_colors = enabled;
+        self
+    }
+
+    pub fn with_ready_delay(mut self, delay: Duration) -> Self {
+        self.ready_delay = delay;
+        self
+    }
+
+    fn tile_style(&self, cell: (usize, usize), flashing: bool, cursor: bool) -> Style {
+        let style = Style::default();
+        if !self.tile_colors {
+            return if flashing {
+                style
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD)
+            } else if cursor {
+                style.fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                style
+            };
+        }
+        let color = tile_color(cell);
+        if flashing {
+            style
+                .bg(color)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else if cursor {
+            style
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            style.fg(color).add_modifier(Modifier::DIM)
         }
     }
 
@@ -65,12 +192,17 @@ impl SequenceState {
     }
 
     fn begin_new_round(&mut self, advance: bool) -> GameAction {
-        if advance {
-            self.sequence.push(random_cell(&mut self.rng));
-        } else if self.sequence.is_empty() {
+        if advance || self.sequence.is_empty() {
             self.sequence.push(random_cell(&mut self.rng));
         }
-        self.start_show();
+        if self.ready_delay.is_zero() {
+            self.start_show();
+        } else {
+            self.idx = 0;
+            self.phase = Phase::Ready {
+                until: Instant::now() + self.ready_delay,
+            };
+        }
         GameAction::None
     }
 
@@ -87,29 +219,57 @@ impl SequenceState {
                         self.best = completed;
                         self.pending_best = Some(completed);
                     }
+                    self.status = format!("✓ {completed} tiles · next round");
                     self.begin_new_round(true);
                 } else {
                     self.status = format!("{} / {}", self.idx, self.sequence.len());
                 }
             } else {
-                if self.sequence.len().saturating_sub(1) > self.best {
-                    self.best = self.sequence.len() - 1;
-                    self.pending_best = Some(self.best);
-                }
+                // `best` and `pending_best` only move on completed rounds, so
+                // the flushed score is the longest sequence fully repeated.
                 let record = self.flush_pending_record();
                 self.status = "Wrong square! Starting over".into();
                 self.sequence.clear();
                 self.idx = 0;
                 self.begin_new_round(false);
-                if !matches!(record, GameAction::None) {
-                    return record;
+                if matches!(record, GameAction::None) {
+                    return GameAction::Cue;
                 }
+                return record;
             }
         }
         GameAction::None
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (mut x, mut y) = self.cursor;
+        x = ((x as isize + dx).clamp(0, (GRID - 1) as isize)) as usize;
+        y = ((y as isize + dy).clamp(0, (GRID - 1) as isize)) as usize;
+        self.cursor = (x, y);
+    }
+
+    fn flush_pending_record(&mut self) -> GameAction {
+        if let Some(score) = self.pending_best.take() {
+            GameAction::Record(
+                StatRecord::new("Pattern", score.to_string(), score as f64),
+                GameKind::Sequence,
+            )
+        } else {
+            GameAction::None
+        }
+    }
+}
+
+impl Game for SequenceState {
+    fn kind(&self) -> GameKind {
+        GameKind::Sequence
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Sequence Memory")
             .borders(Borders::ALL)
@@ -123,6 +283,9 @@ impl SequenceState {
             self.best
         ))];
         lines.push(Line::from(self.status.as_str()));
+        if let Phase::Ready { until } = self.phase {
+            lines.push(Line::from(ready_text(until)));
+        }
         let flash_cell = match self.phase {
             Phase::Showing { step, visible, .. } if visible => self.sequence.get(step).copied(),
             _ => None,
@@ -130,16 +293,10 @@ impl SequenceState {
         for y in 0..GRID {
             let mut spans = Vec::with_capacity(GRID * 2);
             for x in 0..GRID {
-                let mut style = Style::default();
-                if Some((x, y)) == flash_cell {
-                    style = style
-                        .bg(Color::Yellow)
-                        .fg(Color::Black)
-                        .add_modifier(Modifier::BOLD);
-                } else if matches!(self.phase, Phase::Input) && (x, y) == self.cursor {
-                    style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
-                }
-                spans.push(Span::styled("■", style));
+                let flashing = Some((x, y)) == flash_cell;
+                let cursor = matches!(self.phase, Phase::Input) && (x, y) == self.cursor;
+                let style = self.tile_style((x, y), flashing, cursor);
+                spans.push(Span::styled(theme::glyph("■", "#"), style));
                 spans.push(Span::raw(" "));
             }
             lines.push(Line::from(spans));
@@ -147,7 +304,7 @@ impl SequenceState {
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
             match key.code {
                 KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1, 0),
@@ -161,56 +318,241 @@ impl SequenceState {
         GameAction::None
     }
 
-    fn move_cursor(&mut self, dx: isize, dy: isize) {
-        let (mut x, mut y) = self.cursor;
-        x = ((x as isize + dx).clamp(0, (GRID - 1) as isize)) as usize;
-        y = ((y as isize + dy).clamp(0, (GRID - 1) as isize)) as usize;
-        self.cursor = (x, y);
-    }
-
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Phase::Showing {
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        if let Phase::Ready { until } = self.phase
+            && now >= until
+        {
+            self.start_show();
+        }
+        let (flash_on, flash_off) = self.pace.flash_timing(self.sequence.len());
+        // Step through every on/off interval that has fully elapsed, moving
+        // `since` by the interval rather than to `now`, so a late tick after a
+        // stall catches up to where the show should be instead of advancing
+        // one step per tick.
+        while let Phase::Showing {
             step,
             visible,
             since,
         } = &mut self.phase
         {
-            if *visible && now.duration_since(*since) >= FLASH_ON {
+            let hold = if *visible { flash_on } else { flash_off };
+            if now.duration_since(*since) < hold {
+                break;
+            }
+            *since += hold;
+            if *visible {
                 *visible = false;
-                *since = now;
-            } else if !*visible && now.duration_since(*since) >= FLASH_OFF {
-                *since = now;
-                if *step + 1 >= self.sequence.len() {
-                    self.phase = Phase::Input;
-                    self.status = "Repeat the pattern".into();
-                } else {
-                    *step += 1;
-                    *visible = true;
-                }
+            } else if *step + 1 >= self.sequence.len() {
+                self.phase = Phase::Input;
+                self.status = "Repeat the pattern".into();
+            } else {
+                *step += 1;
+                *visible = true;
             }
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
+    fn status_line(&self) -> String {
         match self.phase {
             Phase::Input => format!("Repeat {}/{}", self.idx + 1, self.sequence.len()),
             Phase::Showing { .. } => format!("Showing pattern ({} tiles)", self.sequence.len()),
+            Phase::Ready { until } => ready_text(until),
         }
     }
 
-    fn flush_pending_record(&mut self) -> GameAction {
-        if let Some(score) = self.pending_best.take() {
-            GameAction::Record(
-                StatRecord::new("Pattern", score.to_string(), score as f64),
-                GameKind::Sequence,
-            )
-        } else {
-            GameAction::None
+    fn shift_clock(&mut self, paused: Duration) {
+        match &mut self.phase {
+            Phase::Ready { until } => *until += paused,
+            Phase::Showing { since, .. } => *since += paused,
+            Phase::Input => {}
         }
     }
+
+    /// A best reached before quitting is recorded here, since no mistake
+    /// will come to flush it.
+    fn end_session(&mut self) -> GameAction {
+        self.flush_pending_record()
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Showing { .. } | Phase::Ready { .. })
+    }
+
+    fn snapshot(&self) -> Option<Snapshot> {
+        if self.sequence.len() <= 1 {
+            return None;
+        }
+        Some(Snapshot::Sequence(SequenceSnapshot {
+            seed: self.seed,
+            sequence: self.sequence.clone(),
+            best: self.best,
+            // `end_session` records it when the app closes the game.
+            pending_best: None,
+        }))
+    }
+}
+
+fn ready_text(until: Instant) -> String {
+    let left = until.saturating_duration_since(Instant::now());
+    format!("Get ready… {:.1}s", left.as_secs_f64())
+}
+
+fn tile_color((x, y): (usize, usize)) -> Color {
+    TILE_COLORS[y * GRID + x]
 }
 
 fn random_cell(rng: &mut StdRng) -> (usize, usize) {
     (rng.gen_range(0..GRID), rng.gen_range(0..GRID))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Types the whole pattern back and returns what the last tile gave.
+    fn repeat(state: &mut SequenceState) -> GameAction {
+        state.phase = Phase::Input;
+        let mut action = GameAction::None;
+        for cell in state.sequence.clone() {
+            state.cursor = cell;
+            action = state.handle_selection();
+        }
+        action
+    }
+
+    /// Picks a tile other than the expected one.
+    fn miss(state: &mut SequenceState) -> GameAction {
+        state.phase = Phase::Input;
+        let expected = state.sequence[state.idx];
+        state.cursor = ((expected.0 + 1) % GRID, expected.1);
+        state.handle_selection()
+    }
+
+    fn recorded(action: GameAction) -> Option<f64> {
+        match action {
+            GameAction::Record(record, GameKind::Sequence) => Some(record.score),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn first_attempt_miss_records_nothing() {
+        let mut state = SequenceState::with_seed(1);
+        assert!(matches!(miss(&mut state), GameAction::Cue));
+        assert_eq!(state.best, 0);
+        assert_eq!(recorded(state.end_session()), None);
+    }
+
+    #[test]
+    fn miss_after_four_records_four() {
+        let mut state = SequenceState::with_seed(2);
+        for _ in 0..4 {
+            assert!(matches!(repeat(&mut state), GameAction::None));
+        }
+        assert_eq!(state.sequence.len(), 5);
+        assert_eq!(recorded(miss(&mut state)), Some(4.0));
+        assert_eq!(recorded(state.end_session()), None);
+    }
+
+    #[test]
+    fn consecutive_completions_flush_once_on_quit() {
+        let mut state = SequenceState::with_seed(3);
+        repeat(&mut state);
+        repeat(&mut state);
+        repeat(&mut state);
+        assert_eq!(state.best, 3);
+        assert_eq!(recorded(state.end_session()), Some(3.0));
+        assert_eq!(recorded(state.end_session()), None);
+    }
+
+    #[test]
+    fn late_tick_catches_up_without_skipping() {
+        let mut state = SequenceState::with_seed(4);
+        for _ in 0..5 {
+            state.sequence.push(random_cell(&mut state.rng));
+        }
+        let (on, off) = state.pace.flash_timing(state.sequence.len());
+        let start = Instant::now();
+        state.phase = Phase::Showing {
+            step: 0,
+            visible: true,
+            since: start,
+        };
+        state.handle_tick(start + (on + off) * 5);
+        match state.phase {
+            Phase::Showing { step, visible, .. } => assert_eq!((step, visible), (5, true)),
+            _ => panic!("the last tile is still to be shown"),
+        }
+        state.handle_tick(start + (on + off) * 6);
+        assert!(matches!(state.phase, Phase::Input));
+    }
+
+    #[test]
+    fn longer_patterns_flash_faster_down_to_the_floor() {
+        let (on_short, off_short) = SequencePace::Normal.flash_timing(2);
+        let (on_long, off_long) = SequencePace::Normal.flash_timing(10);
+        assert!(on_long < on_short && off_long < off_short);
+
+        assert_eq!(SequencePace::Steady.flash_timing(10), (FLASH_ON, FLASH_OFF));
+        assert_eq!(
+            SequencePace::Fast.flash_timing(200),
+            (FLASH_ON_FLOOR, FLASH_OFF_FLOOR)
+        );
+    }
+
+    #[test]
+    fn each_cell_has_its_own_color() {
+        let state = SequenceState::with_seed(1);
+        assert_ne!(tile_color((0, 0)), tile_color((1, 0)));
+        assert_ne!(
+            state.tile_style((0, 0), true, false).bg,
+            state.tile_style((2, 1), true, false).bg
+        );
+
+        let mono = SequenceState::with_seed(1).with_tile_colors(false);
+        assert_eq!(
+            mono.tile_style((0, 0), true, false),
+            mono.tile_style((2, 1), true, false)
+        );
+    }
+
+    #[test]
+    fn ascii_mode_draws_the_grid_with_hashes() {
+        let state = SequenceState::with_seed(1);
+        let grid = |state: &SequenceState| {
+            let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(30, 10)).unwrap();
+            terminal
+                .draw(|frame| state.render(frame, frame.size()))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..10)
+                .flat_map(|y| (0..30).map(move |x| (x, y)))
+                .map(|(x, y)| buffer.get(x, y).symbol().to_string())
+                .collect::<String>()
+        };
+        theme::set_ascii(true);
+        let ascii = grid(&state);
+        theme::set_ascii(false);
+        assert!(ascii.contains("# # #"));
+        assert!(!ascii.contains('■'));
+        assert!(grid(&state).contains("■ ■ ■"));
+    }
+
+    #[test]
+    fn next_round_waits_for_the_ready_delay() {
+        let mut state = SequenceState::with_seed(2).with_ready_delay(Duration::from_millis(700));
+        repeat(&mut state);
+        let Phase::Ready { until } = state.phase else {
+            panic!("a completed round pauses before the next one");
+        };
+        state.handle_tick(until - Duration::from_millis(1));
+        assert!(matches!(state.phase, Phase::Ready { .. }));
+        state.handle_tick(until);
+        assert!(matches!(state.phase, Phase::Showing { .. }));
+
+        let mut instant = SequenceState::with_seed(2).with_ready_delay(Duration::ZERO);
+        repeat(&mut instant);
+        assert!(matches!(instant.phase, Phase::Showing { .. }));
+    }
+}
@@ -0,0 +1,291 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use ratatui::prelude::*;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use super::{
+    Game, GameAction, GameKind, StatRecord,
+    navigation::{VimMotionState, relative_gutter},
+};
+use crate::theme;
+
+const GRID: usize = 5;
+const BASE_SPAN: usize = 2;
+const FLASH_ON: Duration = Duration::from_millis(600);
+const FLASH_OFF: Duration = Duration::from_millis(250);
+
+#[derive(Debug)]
+pub struct SpatialSpanState {
+    sequence: Vec<(usize, usize)>,
+    entered: Vec<(usize, usize)>,
+    cursor: (usize, usize),
+    span: usize,
+    best: usize,
+    seed: u64,
+    rng: StdRng,
+    phase: Phase,
+    status: String,
+    nav: VimMotionState,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Showing {
+        step: usize,
+        visible: bool,
+        since: Instant,
+    },
+    Input,
+    Result,
+}
+
+impl SpatialSpanState {
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
+        let mut state = Self {
+            seed,
+            sequence: Vec::new(),
+            entered: Vec::new(),
+            cursor: (0, 0),
+            span: BASE_SPAN,
+            best: 0,
+            rng,
+            phase: Phase::Input,
+            status: String::new(),
+            nav: VimMotionState::default(),
+        };
+        state.generate_sequence();
+        state
+    }
+
+    fn generate_sequence(&mut self) {
+        let mut cells = Vec::with_capacity(GRID * GRID);
+        for x in 0..GRID {
+            for y in 0..GRID {
+                cells.push((x, y));
+            }
+        }
+        cells.shuffle(&mut self.rng);
+        self.sequence = cells.into_iter().take(self.span.min(GRID * GRID)).collect();
+        self.entered.clear();
+        self.nav.clear();
+        self.phase = Phase::Showing {
+            step: 0,
+            visible: true,
+            since: Instant::now(),
+        };
+        self.status = format!("Span {} · watch the order", self.span);
+    }
+
+    fn select(&mut self) -> GameAction {
+        if !matches!(self.phase, Phase::Input) {
+            return GameAction::None;
+        }
+        self.nav.clear();
+        let expected = self.sequence[self.entered.len()];
+        if self.cursor != expected {
+            self.phase = Phase::Result;
+            self.status = format!(
+                "Wrong block · longest span {} · enter or r to restart",
+                self.span - 1
+            );
+            return GameAction::None;
+        }
+        self.entered.push(self.cursor);
+        if self.entered.len() < self.sequence.len() {
+            self.status = format!("{} / {}", self.entered.len(), self.sequence.len());
+            return GameAction::None;
+        }
+        let completed = self.span;
+        self.span += 1;
+        self.generate_sequence();
+        if completed > self.best {
+            self.best = completed;
+            return GameAction::Record(
+                StatRecord::new("Span", completed.to_string(), completed as f64),
+                GameKind::SpatialSpan,
+            );
+        }
+        GameAction::None
+    }
+}
+
+impl Game for SpatialSpanState {
+    fn kind(&self) -> GameKind {
+        GameKind::SpatialSpan
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("Spatial Span")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines = vec![Line::from(format!(
+            "Span {} · Best {}",
+            self.span, self.best
+        ))];
+        lines.push(Line::from(self.status.as_str()));
+        let flash_cell = match self.phase {
+            Phase::Showing { step, visible, .. } if visible => self.sequence.get(step).copied(),
+            _ => None,
+        };
+        let gutter = relative_gutter(self.cursor.1, GRID);
+        for (y, number) in gutter.into_iter().enumerate() {
+            let mut spans = Vec::with_capacity(GRID * 2 + 1);
+            let gutter_style = if y == self.cursor.1 {
+                theme::highlight(Style::default().fg(Color::Yellow))
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(number, gutter_style));
+            for x in 0..GRID {
+                let mut style = Style::default();
+                if Some((x, y)) == flash_cell {
+                    style = style
+                        .bg(Color::Yellow)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD);
+                } else if matches!(self.phase, Phase::Input) && (x, y) == self.cursor {
+                    style = theme::highlight(style.fg(Color::Yellow).add_modifier(Modifier::BOLD));
+                } else if self.entered.contains(&(x, y)) {
+                    style = theme::correct();
+                }
+                spans.push(Span::styled(theme::glyph("■", "#"), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> GameAction {
+        if let Event::Key(key) = event {
+            if self.nav.handle_key(key, &mut self.cursor, GRID, GRID, None) {
+                return GameAction::None;
+            }
+
+            match key.code {
+                KeyCode::Enter if matches!(self.phase, Phase::Result) => {
+                    self.restart_finished();
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => return self.select(),
+                KeyCode::Esc => self.nav.clear(),
+                _ => {}
+            }
+        }
+        GameAction::None
+    }
+
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        // Catch up on every interval that elapsed, as Sequence Memory does.
+        while let Phase::Showing {
+            step,
+            visible,
+            since,
+        } = &mut self.phase
+        {
+            let hold = if *visible { FLASH_ON } else { FLASH_OFF };
+            if now.duration_since(*since) < hold {
+                break;
+            }
+            *since += hold;
+            if *visible {
+                *visible = false;
+            } else if *step + 1 >= self.sequence.len() {
+                self.phase = Phase::Input;
+                self.status = "Repeat the blocks in the same order".into();
+            } else {
+                *step += 1;
+                *visible = true;
+            }
+        }
+        GameAction::None
+    }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Phase::Showing { since, .. } = &mut self.phase {
+            *since += paused;
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !matches!(self.phase, Phase::Result) {
+            return false;
+        }
+        self.span = BASE_SPAN;
+        self.generate_sequence();
+        true
+    }
+
+    fn status_line(&self) -> String {
+        let base = match self.phase {
+            Phase::Showing { .. } => format!("Showing span {}", self.span),
+            Phase::Input => format!(
+                "Repeat {}/{} · best {}",
+                self.entered.len() + 1,
+                self.sequence.len(),
+                self.best
+            ),
+            Phase::Result => self.status.clone(),
+        };
+        if let Some(count) = self.nav.prefix() {
+            format!("{} · count {}", base, count)
+        } else {
+            base
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Showing { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enter(state: &mut SpatialSpanState, cells: &[(usize, usize)]) -> GameAction {
+        let mut action = GameAction::None;
+        for &cell in cells {
+            state.cursor = cell;
+            action = state.select();
+        }
+        action
+    }
+
+    #[test]
+    fn correct_order_advances_and_wrong_order_ends_the_span() {
+        let mut state = SpatialSpanState::with_seed(9);
+        state.phase = Phase::Input;
+        let sequence = state.sequence.clone();
+        match enter(&mut state, &sequence) {
+            GameAction::Record(record, GameKind::SpatialSpan) => assert_eq!(record.score, 2.0),
+            _ => panic!("a first completed span is a best"),
+        }
+        assert_eq!(state.span, BASE_SPAN + 1);
+        assert!(matches!(state.phase, Phase::Showing { .. }));
+
+        state.phase = Phase::Input;
+        let mut reversed = state.sequence.clone();
+        reversed.reverse();
+        assert!(matches!(
+            enter(&mut state, &reversed[..1]),
+            GameAction::None
+        ));
+        assert!(matches!(state.phase, Phase::Result));
+    }
+}
@@ -0,0 +1,234 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use ratatui::prelude::*;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{Game, GameAction, GameKind, StatRecord, typing_game::styled_prompt};
+
+const DRILLS: &[&str] = &[
+    "fn main() -> Result<(), Box<dyn Error>>",
+    "#[derive(Debug, Clone, Copy)]",
+    "let v: Vec<Option<&str>> = vec![];",
+    "items.iter().map(|x| x * 2).sum::<u32>()",
+    "if (a && !b) || c[i] != 0 { return; }",
+    "impl<'a> From<&'a str> for Token<'a>",
+    "match key { Some(k) => k, None => 0 }",
+    "const MAX_RETRY: u8 = 0x1F;",
+    "HashMap<String, Vec<(u8, i64)>>",
+    "user@Host:~/src$ grep -rn 'TODO' .",
+    "x += y % 7 ^ (z << 2) & 0b1010;",
+    "PascalCase snake_case SCREAMING_CASE",
+    "println!(\"{name:>8} = {value:.3}\");",
+    "<div class=\"card\" id='main'></div>",
+    "SELECT * FROM runs WHERE ms < 250;",
+];
+
+#[derive(Debug)]
+pub struct TypingAccuracyState {
+    prompt: &'static str,
+    typed: String,
+    seed: u64,
+    rng: StdRng,
+    started: Option<Instant>,
+    finished: Option<Duration>,
+    resets: u32,
+    best_ms: Option<f64>,
+    status: String,
+}
+
+impl TypingAccuracyState {
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let prompt = DRILLS[rng.gen_range(0..DRILLS.len())];
+        Self {
+            seed,
+            prompt,
+            typed: String::new(),
+            rng,
+            started: None,
+            finished: None,
+            resets: 0,
+            best_ms: None,
+            status: "Type the string without a single mistake".into(),
+        }
+    }
+
+    fn next_drill(&mut self) {
+        let current = self.prompt;
+        while self.prompt == current {
+            self.prompt = DRILLS[self.rng.gen_range(0..DRILLS.len())];
+        }
+        self.typed.clear();
+        self.started = None;
+        self.finished = None;
+        self.resets = 0;
+        self.status = "Type the string without a single mistake".into();
+    }
+
+    fn type_char(&mut self, ch: char) -> GameAction {
+        if self.finished.is_some() {
+            return GameAction::None;
+        }
+        let started = *self.started.get_or_insert_with(Instant::now);
+        self.typed.push(ch);
+        let expected = self.prompt.graphemes(true).nth(self.typed_len() - 1);
+        let actual = self.typed.graphemes(true).next_back();
+        if expected != actual {
+            self.typed.clear();
+            self.started = None;
+            self.resets += 1;
+            self.status = format!(
+                "Mistake! expected {:?} · start over",
+                expected.unwrap_or("")
+            );
+            return GameAction::None;
+        }
+        if self.typed == self.prompt {
+            return self.finish(started.elapsed());
+        }
+        GameAction::None
+    }
+
+    fn finish(&mut self, elapsed: Duration) -> GameAction {
+        self.finished = Some(elapsed);
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        self.status = format!(
+            "Clean in {ms:.0} ms after {} resets · enter or r for the next string",
+            self.resets
+        );
+        if self.best_ms.map(|best| ms < best).unwrap_or(true) {
+            self.best_ms = Some(ms);
+            return GameAction::Record(
+                StatRecord::new("CleanTime", format!("{ms:.0} ms"), ms),
+                GameKind::TypingAccuracy,
+            );
+        }
+        GameAction::None
+    }
+
+    fn typed_len(&self) -> usize {
+        self.typed.graphemes(true).count()
+    }
+
+    fn elapsed(&self) -> Duration {
+        match (self.finished, self.started) {
+            (Some(elapsed), _) => elapsed,
+            (None, Some(start)) => start.elapsed(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+}
+
+impl Game for TypingAccuracyState {
+    fn kind(&self) -> GameKind {
+        GameKind::TypingAccuracy
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("Typing Accuracy")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines = vec![styled_prompt(self.prompt, &self.typed)];
+        lines.push(Line::from(self.status.as_str()));
+        lines.push(Line::from(format!(
+            "Clean streak {}/{} chars · Resets {} · Time {:.1}s",
+            self.typed_len(),
+            self.prompt.graphemes(true).count(),
+            self.resets,
+            self.elapsed().as_secs_f64()
+        )));
+        if let Some(best) = self.best_ms {
+            lines.push(Line::from(format!("Best clean run: {best:.0} ms")));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> GameAction {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char(ch) if !ch.is_control() => return self.type_char(ch),
+                KeyCode::Enter if self.finished.is_some() => self.next_drill(),
+                _ => {}
+            }
+        }
+        GameAction::None
+    }
+
+    fn handle_tick(&mut self, _now: Instant) -> GameAction {
+        GameAction::None
+    }
+
+    fn status_line(&self) -> String {
+        if self.finished.is_some() {
+            self.status.clone()
+        } else {
+            format!(
+                "{}/{} clean · {} resets · {:.1}s",
+                self.typed_len(),
+                self.prompt.graphemes(true).count(),
+                self.resets,
+                self.elapsed().as_secs_f64()
+            )
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if self.finished.is_none() {
+            return false;
+        }
+        self.next_drill();
+        true
+    }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Some(started) = &mut self.started {
+            *started += paused;
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.started.is_some() && self.finished.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn a_mistake_resets_progress_to_zero() {
+        let mut state = TypingAccuracyState::with_seed(3);
+        let prompt: Vec<char> = state.prompt.chars().collect();
+        for &ch in &prompt[..4] {
+            state.handle_event(&key(KeyCode::Char(ch)));
+        }
+        assert_eq!(state.typed_len(), 4);
+        let wrong = if prompt[4] == '~' { '`' } else { '~' };
+        state.handle_event(&key(KeyCode::Char(wrong)));
+        assert_eq!(state.typed_len(), 0);
+        assert_eq!(state.resets, 1);
+        assert!(state.started.is_none());
+    }
+}
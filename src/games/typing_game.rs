@@ -5,9 +5,12 @@ use rand::{Rng, SeedableRng, rngs::StdRng};
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use serde::{Deserialize, Serialize};
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{GameAction, GameKind, StatRecord};
+use super::{Game, GameAction, GameKind, Snapshot, StatRecord};
+use crate::keymap::{self, Action};
+use crate::theme;
 
 const WORD_BANK: &[&str] = &[
     "group",
@@ -92,6 +95,29 @@ const WORD_BANK: &[&str] = &[
 ];
 const WORD_COUNT: usize = 80;
 const ROUND_DURATION: Duration = Duration::from_secs(30);
+const TRIM_THRESHOLD: usize = 160;
+const TRIM_KEEP: usize = 40;
+/// Mistyped words listed after a round.
+const MISTAKES_SHOWN: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypingSnapshot {
+    seed: u64,
+    prompt: String,
+    typed: String,
+    typed_len: usize,
+    trimmed_len: usize,
+    trimmed_correct: usize,
+    elapsed_ms: u64,
+    round_secs: u64,
+    wpm_best: f64,
+    #[serde(default)]
+    round: u64,
+    #[serde(default = "one")]
+    chunks: u64,
+    #[serde(default)]
+    mistakes: Vec<(String, String)>,
+}
 
 #[derive(Debug)]
 pub struct TypingState {
@@ -99,7 +125,20 @@ pub struct TypingState {
     prompt_len: usize,
     typed: String,
     typed_len: usize,
-    rng: StdRng,
+    trimmed_len: usize,
+    trimmed_correct: usize,
+    seed: u64,
+    /// Rounds started since the seed was set, and prompt chunks drawn this
+    /// round. Together with the seed they fix every word, so two players on
+    /// the same seed see the same text however fast they type.
+    round: u64,
+    chunks: u64,
+    /// Text supplied at launch (`--stdin`). It is the whole prompt: no random
+    /// words are appended and finishing it ends the round.
+    custom: Option<String>,
+    /// Mistyped words as (typed, expected), from text already trimmed off
+    /// the front and, once the round ends, from the rest.
+    mistakes: Vec<(String, String)>,
     started: Option<Instant>,
     finished: Option<Instant>,
     wpm_best: f64,
@@ -109,36 +148,91 @@ pub struct TypingState {
 
 impl TypingState {
     pub fn new() -> Self {
-        let mut rng = StdRng::from_entropy();
-        let prompt = generate_prompt(&mut rng);
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let prompt = prompt_chunk(seed, 0, 0);
         let prompt_len = prompt.graphemes(true).count();
         Self {
+            seed,
             prompt,
             prompt_len,
             typed: String::new(),
             typed_len: 0,
-            rng,
+            trimmed_len: 0,
+            trimmed_correct: 0,
+            round: 0,
+            chunks: 1,
+            custom: None,
+            mistakes: Vec::new(),
             started: None,
             finished: None,
             wpm_best: 0.0,
-            status: "30s typing sprint · start typing to begin".into(),
+            status: idle_status(ROUND_DURATION),
             timer_duration: ROUND_DURATION,
         }
     }
 
+    /// A sprint over `text` instead of random words. Line breaks cannot be
+    /// typed, so runs of whitespace become single spaces.
+    pub fn from_text(text: &str) -> Self {
+        let mut state = Self::new();
+        let prompt = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        state.prompt_len = prompt.graphemes(true).count();
+        state.prompt = prompt.clone();
+        state.custom = Some(prompt);
+        state
+    }
+
+    /// Picks a sprint back up with the clock shifted so the elapsed time
+    /// carries over.
+    pub fn restore(snapshot: TypingSnapshot) -> Self {
+        let mut state = Self::with_seed(snapshot.seed)
+            .with_round_duration(Duration::from_secs(snapshot.round_secs));
+        state.prompt_len = snapshot.prompt.graphemes(true).count();
+        state.prompt = snapshot.prompt;
+        state.typed = snapshot.typed;
+        state.typed_len = snapshot.typed_len;
+        state.trimmed_len = snapshot.trimmed_len;
+        state.trimmed_correct = snapshot.trimmed_correct;
+        state.wpm_best = snapshot.wpm_best;
+        state.round = snapshot.round;
+        state.chunks = snapshot.chunks;
+        state.mistakes = snapshot.mistakes;
+        state.started = Some(Instant::now() - Duration::from_millis(snapshot.elapsed_ms));
+        state.status = "Resumed · keep typing".into();
+        state
+    }
+
+    pub fn with_round_duration(mut self, duration: Duration) -> Self {
+        self.timer_duration = duration;
+        self.status = idle_status(duration);
+        self
+    }
+
     fn restart(&mut self) {
-        self.prompt = generate_prompt(&mut self.rng);
+        self.round += 1;
+        self.chunks = 1;
+        self.prompt = match &self.custom {
+            Some(text) => text.clone(),
+            None => prompt_chunk(self.seed, self.round, 0),
+        };
         self.prompt_len = self.prompt.graphemes(true).count();
         self.typed.clear();
         self.typed_len = 0;
+        self.mistakes.clear();
+        self.trimmed_len = 0;
+        self.trimmed_correct = 0;
         self.started = None;
         self.finished = None;
-        self.status = "30s typing sprint · start typing to begin".into();
+        self.status = idle_status(self.timer_duration);
     }
 
     fn ensure_prompt_capacity(&mut self) {
-        if self.prompt_len.saturating_sub(self.typed_len) < 10 {
-            let extra = generate_prompt(&mut self.rng);
+        if self.custom.is_none() && self.prompt_len.saturating_sub(self.typed_len) < 10 {
+            let extra = prompt_chunk(self.seed, self.round, self.chunks);
+            self.chunks += 1;
             if !self.prompt.ends_with(' ') {
                 self.prompt.push(' ');
             }
@@ -147,20 +241,79 @@ impl TypingState {
         }
     }
 
-    fn accuracy(&self) -> f64 {
-        if self.typed.is_empty() {
-            return 100.0;
+    /// Drops whole words that were typed long ago from the front of both the
+    /// prompt and the input so a long sprint keeps a bounded buffer. Their
+    /// length and correct count are folded into running totals.
+    fn trim_consumed(&mut self) {
+        if self.typed_len <= TRIM_THRESHOLD {
+            return;
         }
-        let total = self.typed_len.max(1);
-        let correct = self
+        let limit = self.typed_len - TRIM_KEEP;
+        let Some(cut) = self
+            .prompt
+            .graphemes(true)
+            .take(limit)
+            .enumerate()
+            .filter(|(_, g)| *g == " ")
+            .map(|(idx, _)| idx + 1)
+            .last()
+        else {
+            return;
+        };
+        self.trimmed_correct += self
             .typed
             .graphemes(true)
             .zip(self.prompt.graphemes(true))
+            .take(cut)
             .filter(|(a, b)| a == b)
             .count();
+        let prompt_cut: String = self.prompt.graphemes(true).take(cut).collect();
+        let typed_cut: String = self.typed.graphemes(true).take(cut).collect();
+        self.mistakes
+            .extend(mistyped_words(&prompt_cut, &typed_cut));
+        self.trimmed_len += cut;
+        self.typed = self.typed.graphemes(true).skip(cut).collect();
+        self.prompt = self.prompt.graphemes(true).skip(cut).collect();
+        self.typed_len -= cut;
+        self.prompt_len -= cut;
+    }
+
+    fn total_typed(&self) -> usize {
+        self.trimmed_len + self.typed_len
+    }
+
+    /// Graphemes typed and how many of them match the prompt. Input that
+    /// runs past the end of the prompt has nothing to match and counts as
+    /// wrong; anything fixed with backspace is no longer in `typed`.
+    fn typed_counts(&self) -> (usize, usize) {
+        let typed = self.typed.graphemes(true).count();
+        let mut compared = 0;
+        let mut correct = 0;
+        for (a, b) in self.typed.graphemes(true).zip(self.prompt.graphemes(true)) {
+            compared += 1;
+            if a == b {
+                correct += 1;
+            }
+        }
+        (
+            self.trimmed_len + typed.max(compared),
+            self.trimmed_correct + correct,
+        )
+    }
+
+    fn accuracy(&self) -> f64 {
+        let (total, correct) = self.typed_counts();
+        if total == 0 {
+            return 100.0;
+        }
         100.0 * correct as f64 / total as f64
     }
 
+    fn uncorrected_errors(&self) -> usize {
+        let (total, correct) = self.typed_counts();
+        total - correct
+    }
+
     fn finish_round(&mut self, elapsed: Duration) -> GameAction {
         if self.finished.is_some() {
             return GameAction::None;
@@ -169,26 +322,38 @@ impl TypingState {
             .max(Duration::from_millis(100))
             .min(self.timer_duration);
         let minutes = elapsed.as_secs_f64() / 60.0;
+        // Gross counts every keystroke as a fifth of a word; net then takes
+        // off one word per minute for each error left uncorrected.
+        let gross = if minutes > 0.0 {
+            (self.total_typed() as f64 / 5.0) / minutes
+        } else {
+            0.0
+        };
         let wpm = if minutes > 0.0 {
-            (self.typed_len as f64 / 5.0) / minutes
+            (gross - self.uncorrected_errors() as f64 / minutes).max(0.0)
         } else {
             0.0
         };
         let acc = self.accuracy();
+        let rest = mistyped_words(&self.prompt, &self.typed);
+        self.mistakes.extend(rest);
         self.status = format!(
-            "Time! {:.1} WPM · {:.1}% accuracy · {} chars",
-            wpm, acc, self.typed_len
+            "Time! {:.1} WPM (raw {:.1}) · {:.1}% accuracy · {} chars · r to restart",
+            wpm,
+            gross,
+            acc,
+            self.total_typed()
         );
         let finish_time = self.started.unwrap_or_else(Instant::now) + elapsed;
         self.finished = Some(finish_time);
         if acc >= 80.0 && wpm > self.wpm_best {
             self.wpm_best = wpm;
             return GameAction::Record(
-                StatRecord::new("WPM", format!("{wpm:.1}"), wpm),
+                StatRecord::new("Net WPM", format!("{wpm:.1} (raw {gross:.1})"), wpm),
                 GameKind::Typing,
             );
         }
-        GameAction::None
+        GameAction::Cue
     }
 
     fn remaining_time(&self) -> Duration {
@@ -206,8 +371,19 @@ impl TypingState {
             self.timer_duration
         }
     }
+}
+
+impl Game for TypingState {
+    fn kind(&self) -> GameKind {
+        GameKind::Typing
+    }
+
+    /// Custom text does not come from the seed, so there is none to share.
+    fn seed(&self) -> Option<u64> {
+        self.custom.is_none().then_some(self.seed)
+    }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Typing")
             .borders(Borders::ALL)
@@ -215,67 +391,59 @@ impl TypingState {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        let prompt_chars: Vec<String> =
-            self.prompt.graphemes(true).map(|g| g.to_string()).collect();
-        let typed_chars: Vec<String> = self.typed.graphemes(true).map(|g| g.to_string()).collect();
-        let mut spans = Vec::with_capacity(prompt_chars.len());
-        for (idx, ch) in prompt_chars.iter().enumerate() {
-            let style = if idx < typed_chars.len() {
-                if typed_chars[idx] == *ch {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default()
-                        .fg(Color::Red)
-                        .add_modifier(Modifier::UNDERLINED)
-                }
-            } else if idx == typed_chars.len() {
-                Style::default()
-                    .bg(Color::Yellow)
-                    .fg(Color::Black)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
-            spans.push(Span::styled(ch.clone(), style));
-        }
-
-        let mut lines = vec![Line::from(spans)];
+        let mut lines = vec![styled_prompt(&self.prompt, &self.typed)];
         lines.push(Line::from(self.status.as_str()));
         let remaining = self.remaining_time();
         lines.push(Line::from(format!(
             "Time left {:>5.1}s · Accuracy {:>5.1}% · Typed {} chars",
             remaining.as_secs_f64().max(0.0),
             self.accuracy(),
-            self.typed_len
+            self.total_typed()
         )));
-        lines.push(Line::from(format!("Best {:.1} WPM", self.wpm_best)));
+        lines.push(Line::from(format!("Best {:.1} net WPM", self.wpm_best)));
+        if self.finished.is_some() && !self.mistakes.is_empty() {
+            let mut spans = vec![Span::raw(format!(
+                "Mistyped words ({}): ",
+                self.mistakes.len()
+            ))];
+            for (idx, (typed, expected)) in self.mistakes.iter().take(MISTAKES_SHOWN).enumerate() {
+                if idx > 0 {
+                    spans.push(Span::raw(" · "));
+                }
+                spans.push(Span::styled(typed.clone(), theme::incorrect()));
+                spans.push(Span::raw(format!(" → {expected}")));
+            }
+            lines.push(Line::from(spans));
+        }
         frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
             match key.code {
-                KeyCode::Char(ch) if !ch.is_control() => {
-                    if self.finished.is_none() {
-                        if self.started.is_none() {
-                            self.started = Some(Instant::now());
-                            self.status = "Timer running · keep typing".into();
-                        }
-                        self.typed.push(ch);
-                        self.typed_len += 1;
-                        self.ensure_prompt_capacity();
+                KeyCode::Char(ch) if !ch.is_control() && self.finished.is_none() => {
+                    if self.started.is_none() {
+                        self.started = Some(Instant::now());
+                        self.status = "Timer running · keep typing".into();
                     }
-                }
-                KeyCode::Backspace => {
-                    if self.finished.is_none() {
-                        if self.typed.pop().is_some() {
-                            self.typed_len = self.typed_len.saturating_sub(1);
-                        }
+                    self.typed.push(ch);
+                    self.typed_len += 1;
+                    self.trim_consumed();
+                    self.ensure_prompt_capacity();
+                    if self.custom.is_some()
+                        && self.typed_len >= self.prompt_len
+                        && let Some(start) = self.started
+                    {
+                        return self.finish_round(Instant::now().saturating_duration_since(start));
                     }
                 }
-                KeyCode::Enter => {
+                KeyCode::Backspace if self.finished.is_none() => {
+                    self.typed.pop();
+                    self.typed_len = self.typed_len.saturating_sub(1);
+                }
+                code if keymap::current().is(code, Action::Submit) => {
                     if let Some(start) = self.started {
-                        if let Some(_) = self.finished {
+                        if self.finished.is_some() {
                             self.restart();
                         } else {
                             let elapsed = Instant::now().saturating_duration_since(start);
@@ -291,30 +459,91 @@ impl TypingState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Some(start) = self.started {
-            if self.finished.is_none() {
-                let elapsed = now.saturating_duration_since(start);
-                if elapsed >= self.timer_duration {
-                    return self.finish_round(self.timer_duration);
-                }
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        if let Some(start) = self.started
+            && self.finished.is_none()
+        {
+            let elapsed = now.saturating_duration_since(start);
+            if elapsed >= self.timer_duration {
+                return self.finish_round(self.timer_duration);
             }
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
+    fn status_line(&self) -> String {
         if self.finished.is_some() {
             self.status.clone()
         } else {
             format!(
                 "Time left {:>4.1}s · Typed {} chars · {:.1}% accuracy",
                 self.remaining_time().as_secs_f64().max(0.0),
-                self.typed_len,
+                self.total_typed(),
                 self.accuracy()
             )
         }
     }
+
+    fn restart_finished(&mut self) -> bool {
+        if self.finished.is_none() {
+            return false;
+        }
+        self.restart();
+        true
+    }
+
+    fn is_animating(&self) -> bool {
+        self.started.is_some() && self.finished.is_none()
+    }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if self.finished.is_none()
+            && let Some(started) = &mut self.started
+        {
+            *started += paused;
+        }
+    }
+
+    fn snapshot(&self) -> Option<Snapshot> {
+        if self.custom.is_some() {
+            return None;
+        }
+        let start = self.started.filter(|_| self.finished.is_none())?;
+        Some(Snapshot::Typing(TypingSnapshot {
+            seed: self.seed,
+            prompt: self.prompt.clone(),
+            typed: self.typed.clone(),
+            typed_len: self.typed_len,
+            trimmed_len: self.trimmed_len,
+            trimmed_correct: self.trimmed_correct,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            round_secs: self.timer_duration.as_secs(),
+            wpm_best: self.wpm_best,
+            round: self.round,
+            chunks: self.chunks,
+            mistakes: self.mistakes.clone(),
+        }))
+    }
+}
+
+fn idle_status(duration: Duration) -> String {
+    format!(
+        "{}s typing sprint · start typing to begin",
+        duration.as_secs()
+    )
+}
+
+fn one() -> u64 {
+    1
+}
+
+/// Each chunk gets its own rng derived from the seed, so the words do not
+/// depend on how many chunks were drawn before it.
+fn prompt_chunk(seed: u64, round: u64, chunk: u64) -> String {
+    let key = seed
+        .wrapping_add(round.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .wrapping_add(chunk.wrapping_mul(0xBF58_476D_1CE4_E5B9));
+    generate_prompt(&mut StdRng::seed_from_u64(key))
 }
 
 fn generate_prompt(rng: &mut StdRng) -> String {
@@ -323,3 +552,173 @@ fn generate_prompt(rng: &mut StdRng) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+/// Words of `prompt` that were typed in full but not exactly, as (typed,
+/// expected). Typing is compared position by position, so each prompt word
+/// is matched with whatever was typed over the same graphemes; a word still
+/// being typed is left out.
+fn mistyped_words(prompt: &str, typed: &str) -> Vec<(String, String)> {
+    let typed: Vec<&str> = typed.graphemes(true).collect();
+    let mut mistakes = Vec::new();
+    let mut start = 0;
+    for word in prompt.split(' ') {
+        let len = word.graphemes(true).count();
+        let end = start + len;
+        if end > typed.len() {
+            break;
+        }
+        let attempt: String = typed[start..end].concat();
+        if attempt != word {
+            mistakes.push((attempt.trim().to_string(), word.to_string()));
+        }
+        start = end + 1;
+    }
+    mistakes
+}
+
+/// Colors each prompt grapheme by whether it was typed correctly, mistyped, is
+/// under the cursor, or is still pending.
+pub(super) fn styled_prompt(prompt: &str, typed: &str) -> Line<'static> {
+    let typed_chars: Vec<&str> = typed.graphemes(true).collect();
+    let spans: Vec<Span<'static>> = prompt
+        .graphemes(true)
+        .enumerate()
+        .map(|(idx, ch)| {
+            let style = if idx < typed_chars.len() {
+                if typed_chars[idx] == ch {
+                    theme::correct()
+                } else {
+                    theme::incorrect()
+                }
+            } else if idx == typed_chars.len() {
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Span::styled(ch.to_string(), style)
+        })
+        .collect();
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn overshoot_counts_as_errors() {
+        let mut state = TypingState::from_text("abc");
+        state.typed = "abcde".into();
+        state.typed_len = 5;
+        assert_eq!(state.typed_counts(), (5, 3));
+        assert_eq!(state.accuracy(), 60.0);
+        assert_eq!(state.uncorrected_errors(), 2);
+    }
+
+    #[test]
+    fn long_sprint_keeps_the_prompt_bounded() {
+        let mut state = TypingState::with_seed(11);
+        for _ in 0..500 {
+            let next = state.prompt.chars().nth(state.typed_len).unwrap();
+            state.handle_event(&Event::Key(KeyEvent::new(
+                KeyCode::Char(next),
+                KeyModifiers::NONE,
+            )));
+        }
+        assert_eq!(state.total_typed(), 500);
+        assert!(state.typed_len <= TRIM_THRESHOLD);
+        // Less text is held than was typed: the consumed words are gone.
+        assert!(state.prompt_len < 500);
+        assert_eq!(state.accuracy(), 100.0);
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_the_input_and_clock() {
+        let mut state = TypingState::with_seed(8);
+        for ch in "the qu".chars() {
+            state.handle_event(&Event::Key(KeyEvent::new(
+                KeyCode::Char(ch),
+                KeyModifiers::NONE,
+            )));
+        }
+        state.started = Some(Instant::now() - Duration::from_secs(5));
+        let Some(Snapshot::Typing(snapshot)) = state.snapshot() else {
+            panic!("a sprint in progress can be resumed");
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored = TypingState::restore(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(restored.typed, "the qu");
+        assert_eq!(restored.typed_len, 6);
+        assert_eq!(restored.prompt, state.prompt);
+        let elapsed = restored.started.unwrap().elapsed();
+        assert!(elapsed >= Duration::from_secs(5) && elapsed < Duration::from_secs(6));
+    }
+
+    #[test]
+    fn typos_are_underlined() {
+        let line = styled_prompt("cat", "cot");
+        let underlined: Vec<bool> = line
+            .spans
+            .iter()
+            .map(|span| span.style.add_modifier.contains(Modifier::UNDERLINED))
+            .collect();
+        assert_eq!(underlined, [false, true, false]);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_prompt_as_it_grows() {
+        let mut first = TypingState::with_seed(42);
+        let mut second = TypingState::with_seed(42);
+        assert_eq!(first.prompt, second.prompt);
+        let initial = first.prompt_len;
+        for state in [&mut first, &mut second] {
+            state.typed_len = state.prompt_len - 5;
+            state.ensure_prompt_capacity();
+        }
+        assert!(first.prompt_len > initial);
+        assert_eq!(first.prompt, second.prompt);
+        assert_ne!(
+            TypingState::with_seed(43).prompt,
+            TypingState::with_seed(42).prompt
+        );
+    }
+
+    #[test]
+    fn net_wpm_takes_a_word_per_minute_off_per_error() {
+        let mut state = TypingState::from_text(&"a".repeat(100));
+        state.timer_duration = Duration::from_secs(60);
+        state.typed = format!("{}{}", "a".repeat(95), "b".repeat(5));
+        state.typed_len = 100;
+        state.started = Some(Instant::now());
+        match state.finish_round(Duration::from_secs(60)) {
+            GameAction::Record(record, GameKind::Typing) => {
+                assert_eq!(record.score, 15.0);
+                assert_eq!(record.value, "15.0 (raw 20.0)");
+            }
+            _ => panic!("a first round at 95% accuracy is a best"),
+        }
+    }
+
+    #[test]
+    fn piped_text_is_the_whole_prompt() {
+        let text = "Sphinx of black quartz, judge my vow.";
+        let mut state = TypingState::from_text(text);
+        assert_eq!(state.prompt, text);
+        state.typed_len = state.prompt_len - 2;
+        state.ensure_prompt_capacity();
+        assert_eq!(state.prompt, text);
+    }
+
+    #[test]
+    fn word_breakdown_lists_only_mistyped_words() {
+        let mistakes = mistyped_words("the quick fox", "the quack fox");
+        assert_eq!(mistakes, vec![("quack".to_string(), "quick".to_string())]);
+        assert!(mistyped_words("the quick fox", "the quick fox").is_empty());
+    }
+}
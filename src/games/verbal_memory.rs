@@ -1,5 +1,5 @@
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
 use crossterm::event::{Event, KeyCode};
 use rand::{Rng, SeedableRng, rngs::StdRng};
@@ -7,53 +7,160 @@ use ratatui::prelude::*;
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use super::{GameAction, GameKind, StatRecord};
+use super::{Game, GameAction, GameKind, StatRecord, Streak};
 
 const WORDS: &[&str] = &[
     "apple", "galaxy", "harbor", "quantum", "saffron", "vector", "marble", "amber", "citadel",
     "nebula", "orchid", "raven", "timber", "glacier", "summit", "horizon", "lantern", "pioneer",
     "anthem", "compass",
 ];
+const IDLE_TICK: Duration = Duration::from_millis(250);
+const DEFAULT_LIVES: u8 = 3;
+/// A word is not drawn again within this many turns of its last showing,
+/// so "seen" answers test memory rather than the previous screen.
+const RECENT_WINDOW: usize = 3;
+
+/// How a run ends: out of lives, or after a fixed number of answers with
+/// mistakes costing nothing but accuracy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerbalMode {
+    Lives(u8),
+    Endless { judgments: u32 },
+}
 
 #[derive(Debug)]
 pub struct VerbalMemoryState {
+    seed: u64,
+    /// Runs started since the seed was set; each draws its words from the
+    /// seed and this, so a seeded session replays the same way.
+    round: u64,
     rng: StdRng,
     seen: HashSet<&'static str>,
     current: &'static str,
+    recent: VecDeque<&'static str>,
     score: u32,
     lives: u8,
     best: u32,
     status: String,
     pending_best: Option<u32>,
+    judgments: u32,
+    correct: u32,
+    mode: VerbalMode,
+    best_accuracy: Option<f64>,
+    streak: Streak,
 }
 
 impl VerbalMemoryState {
     pub fn new() -> Self {
-        let mut rng = StdRng::from_entropy();
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = round_rng(seed, 0);
         let idx = rng.gen_range(0..WORDS.len());
         Self {
+            seed,
+            round: 0,
             rng,
             seen: HashSet::new(),
             current: WORDS[idx],
+            recent: VecDeque::from([WORDS[idx]]),
             score: 0,
-            lives: 3,
+            lives: DEFAULT_LIVES,
             best: 0,
             status: "Press l for NEW, h for SEEN".into(),
             pending_best: None,
+            judgments: 0,
+            correct: 0,
+            mode: VerbalMode::Lives(DEFAULT_LIVES),
+            best_accuracy: None,
+            streak: Streak::default(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: VerbalMode) -> Self {
+        self.mode = mode;
+        if let VerbalMode::Lives(lives) = mode {
+            self.lives = lives.max(1);
+        }
+        self
+    }
+
+    /// Starts the next run in place. The session bests, streak and seed
+    /// carry over.
+    fn restart(&mut self) {
+        self.round += 1;
+        self.rng = round_rng(self.seed, self.round);
+        self.current = WORDS[self.rng.gen_range(0..WORDS.len())];
+        self.recent = VecDeque::from([self.current]);
+        self.seen.clear();
+        self.score = 0;
+        if let VerbalMode::Lives(lives) = self.mode {
+            self.lives = lives.max(1);
+        }
+        self.pending_best = None;
+        self.judgments = 0;
+        self.correct = 0;
+        self.status = "Press l for NEW, h for SEEN".into();
+    }
+
+    fn is_over(&self) -> bool {
+        match self.mode {
+            VerbalMode::Lives(_) => self.lives == 0,
+            VerbalMode::Endless { judgments } => self.judgments >= judgments,
+        }
+    }
+
+    fn mode_text(&self) -> String {
+        match self.mode {
+            VerbalMode::Lives(_) => format!("Lives {}", self.lives),
+            VerbalMode::Endless { judgments } => {
+                format!("Endless {}/{}", self.judgments, judgments)
+            }
         }
     }
 
+    fn accuracy(&self) -> f64 {
+        if self.judgments == 0 {
+            return 100.0;
+        }
+        100.0 * self.correct as f64 / self.judgments as f64
+    }
+
     fn next_word(&mut self) {
-        let idx = self.rng.gen_range(0..WORDS.len());
-        self.current = WORDS[idx];
+        // With a tiny pool only the immediate repeat is ruled out.
+        let window = RECENT_WINDOW.min(WORDS.len().saturating_sub(1)).max(1);
+        let candidates: Vec<&'static str> = WORDS
+            .iter()
+            .copied()
+            .filter(|word| !self.recent.iter().take(window).any(|recent| recent == word))
+            .collect();
+        let pool = if candidates.is_empty() {
+            WORDS
+        } else {
+            &candidates
+        };
+        self.current = pool[self.rng.gen_range(0..pool.len())];
+        self.recent.push_front(self.current);
+        self.recent.truncate(RECENT_WINDOW);
     }
 
     fn evaluate(&mut self, guess_seen: bool) -> GameAction {
-        if self.lives == 0 {
+        if self.is_over() {
             return GameAction::None;
         }
         let was_seen = self.seen.contains(&self.current);
+        self.judgments += 1;
+        if guess_seen == was_seen {
+            self.streak.hit();
+        } else {
+            self.streak.miss();
+        }
+        if let VerbalMode::Endless { .. } = self.mode {
+            return self.evaluate_endless(guess_seen == was_seen);
+        }
         if guess_seen == was_seen {
+            self.correct += 1;
             self.score += 1;
             self.status = "Correct".into();
             self.seen.insert(self.current);
@@ -64,18 +171,80 @@ impl VerbalMemoryState {
         } else {
             self.status = "Wrong!".into();
             self.lives = self.lives.saturating_sub(1);
+            let mut action = GameAction::Cue;
             if self.lives == 0 {
-                self.status = format!("Game over · final score {}", self.score);
-                let action = self.flush_pending_record();
-                self.next_word();
-                return action;
+                self.status = format!("Game over · final score {} · r to restart", self.score);
+                if let record @ GameAction::Record(..) = self.flush_pending_record() {
+                    action = record;
+                }
             }
+            self.next_word();
+            return action;
         }
         self.next_word();
         GameAction::None
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    /// Endless runs never lose lives; they stop after the set number of
+    /// answers and record accuracy instead of score.
+    fn evaluate_endless(&mut self, correct: bool) -> GameAction {
+        self.seen.insert(self.current);
+        let mut action = GameAction::None;
+        if correct {
+            self.correct += 1;
+            self.score += 1;
+            self.best = self.best.max(self.score);
+            self.status = "Correct".into();
+        } else {
+            self.status = "Wrong!".into();
+            action = GameAction::Cue;
+        }
+        if self.is_over() {
+            let accuracy = self.accuracy();
+            self.status = format!(
+                "Done · {:.0}% correct over {} words · r to restart",
+                accuracy, self.judgments
+            );
+            if self.best_accuracy.is_none_or(|best| accuracy > best) {
+                self.best_accuracy = Some(accuracy);
+                action = GameAction::Record(
+                    StatRecord::new("Accuracy", format!("{accuracy:.0}%"), accuracy),
+                    GameKind::VerbalMemory,
+                );
+            }
+        }
+        self.next_word();
+        action
+    }
+
+    /// "Seen 37 · 92% correct"; the rate is left off until the first answer.
+    fn progress_text(&self) -> String {
+        if self.judgments == 0 {
+            return format!("Seen {}", self.seen.len());
+        }
+        format!("Seen {} · {:.0}% correct", self.seen.len(), self.accuracy())
+    }
+
+    fn flush_pending_record(&mut self) -> GameAction {
+        if let Some(score) = self.pending_best.take() {
+            let record = StatRecord::new("Score", score.to_string(), score as f64);
+            GameAction::Record(record, GameKind::VerbalMemory)
+        } else {
+            GameAction::None
+        }
+    }
+}
+
+impl Game for VerbalMemoryState {
+    fn kind(&self) -> GameKind {
+        GameKind::VerbalMemory
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Verbal Memory")
             .borders(Borders::ALL)
@@ -83,12 +252,13 @@ impl VerbalMemoryState {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        let mut lines = vec![Line::from(format!("Lives: {}", self.lives))];
+        let mut lines = vec![Line::from(self.mode_text())];
         lines.push(Line::from(format!(
             "Score: {} (best {})",
             self.score, self.best
         )));
-        if self.lives > 0 {
+        lines.push(Line::from(self.progress_text()));
+        if !self.is_over() {
             lines.push(Line::from("Seen this word before?"));
             lines.push(Line::from(format!("› {}", self.current)));
         } else {
@@ -98,14 +268,13 @@ impl VerbalMemoryState {
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
             match key.code {
                 KeyCode::Char('h') | KeyCode::Left => return self.evaluate(true),
                 KeyCode::Char('l') | KeyCode::Right => return self.evaluate(false),
-                KeyCode::Enter if self.lives == 0 => {
-                    self.pending_best = None;
-                    *self = Self::new();
+                KeyCode::Enter => {
+                    self.restart_finished();
                 }
                 _ => {}
             }
@@ -113,20 +282,126 @@ impl VerbalMemoryState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, _now: Instant) -> GameAction {
+    fn handle_tick(&mut self, _now: Instant) -> GameAction {
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
-        format!("Score {} · Lives {}", self.score, self.lives)
+    fn status_line(&self) -> String {
+        format!(
+            "Score {} · {} · {} · streak {}",
+            self.score,
+            self.mode_text(),
+            self.progress_text(),
+            self.streak.current()
+        )
     }
 
-    fn flush_pending_record(&mut self) -> GameAction {
-        if let Some(score) = self.pending_best.take() {
-            let record = StatRecord::new("Score", score.to_string(), score as f64);
-            GameAction::Record(record, GameKind::VerbalMemory)
-        } else {
-            GameAction::None
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if !self.is_over() {
+            return false;
+        }
+        self.restart();
+        true
+    }
+
+    fn end_session(&mut self) -> GameAction {
+        self.streak.take_record(GameKind::VerbalMemory)
+    }
+
+    /// Nothing here is timed, so a slow tick is plenty.
+    fn desired_tick(&self) -> Option<Duration> {
+        Some(IDLE_TICK)
+    }
+}
+
+/// The generator for run `round` of a session on `seed`; run 0 uses the
+/// seed as is.
+fn round_rng(seed: u64, round: u64) -> StdRng {
+    StdRng::seed_from_u64(seed.wrapping_add(round.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers the current word correctly or not.
+    fn answer(state: &mut VerbalMemoryState, correct: bool) -> GameAction {
+        let seen = state.seen.contains(&state.current);
+        state.evaluate(seen == correct)
+    }
+
+    #[test]
+    fn endless_mode_ends_after_the_set_answers() {
+        let mut state =
+            VerbalMemoryState::with_seed(4).with_mode(VerbalMode::Endless { judgments: 5 });
+        for _ in 0..4 {
+            assert!(matches!(answer(&mut state, false), GameAction::Cue));
+            assert!(!state.is_over());
+        }
+        match answer(&mut state, true) {
+            GameAction::Record(record, _) => {
+                assert_eq!(record.label, "Accuracy");
+                assert_eq!(record.score, 20.0);
+            }
+            _ => panic!("the last answer ends the run"),
+        }
+        assert!(state.is_over());
+        assert!(matches!(answer(&mut state, true), GameAction::None));
+    }
+
+    #[test]
+    fn restart_keeps_bests_and_follows_the_seed() {
+        let play = |state: &mut VerbalMemoryState| {
+            answer(state, true);
+            answer(state, true);
+            while !state.is_over() {
+                answer(state, false);
+            }
+        };
+        let mut state = VerbalMemoryState::with_seed(9);
+        play(&mut state);
+        assert_eq!(state.best, 2);
+        assert!(state.restart_finished());
+        assert_eq!(
+            (state.score, state.lives, state.best),
+            (0, DEFAULT_LIVES, 2)
+        );
+        assert!(state.seen.is_empty());
+        assert_eq!(state.seed, 9);
+
+        let mut replay = VerbalMemoryState::with_seed(9);
+        play(&mut replay);
+        replay.restart_finished();
+        assert_eq!(replay.current, state.current);
+        for _ in 0..10 {
+            answer(&mut state, true);
+            answer(&mut replay, true);
+            assert_eq!(replay.current, state.current);
+        }
+    }
+
+    #[test]
+    fn progress_shows_seen_words_and_the_correct_rate() {
+        let mut state = VerbalMemoryState::with_seed(2);
+        assert_eq!(state.progress_text(), "Seen 0");
+        for correct in [true, true, false, true, false, true] {
+            answer(&mut state, correct);
+        }
+        assert_eq!(state.progress_text(), "Seen 4 · 67% correct");
+    }
+
+    #[test]
+    fn draws_never_repeat_the_previous_word() {
+        let mut state = VerbalMemoryState::with_seed(12);
+        let mut previous = state.current;
+        for _ in 0..100 {
+            state.next_word();
+            assert_ne!(state.current, previous);
+            previous = state.current;
         }
     }
 }
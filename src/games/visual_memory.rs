@@ -7,7 +7,11 @@ use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use super::{GameAction, GameKind, StatRecord, navigation::VimMotionState};
+use super::{
+    Game, GameAction, GameKind, StatRecord, Streak,
+    navigation::{VimMotionState, relative_gutter},
+};
+use crate::theme;
 
 const GRID: usize = 5;
 const BASE_CELLS: usize = 3;
@@ -18,10 +22,15 @@ pub struct VisualMemoryState {
     pattern: HashSet<(usize, usize)>,
     guesses: HashSet<(usize, usize)>,
     cursor: (usize, usize),
+    seed: u64,
     rng: StdRng,
     round: usize,
     best: usize,
     lives: u8,
+    reveal: Duration,
+    /// Re-reveals left this round; each one costs a life.
+    peeks_left: u8,
+    streak: Streak,
     phase: Phase,
     status: String,
     nav: VimMotionState,
@@ -31,13 +40,19 @@ pub struct VisualMemoryState {
 enum Phase {
     Reveal { since: Instant },
     Recall,
+    Peek { since: Instant },
     Result,
 }
 
 impl VisualMemoryState {
     pub fn new() -> Self {
-        let rng = StdRng::from_entropy();
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
         let mut state = Self {
+            seed,
             pattern: HashSet::new(),
             guesses: HashSet::new(),
             cursor: (0, 0),
@@ -45,6 +60,9 @@ impl VisualMemoryState {
             round: 1,
             best: 0,
             lives: 3,
+            reveal: REVEAL,
+            peeks_left: 1,
+            streak: Streak::default(),
             phase: Phase::Reveal {
                 since: Instant::now(),
             },
@@ -55,6 +73,11 @@ impl VisualMemoryState {
         state
     }
 
+    pub fn with_reveal(mut self, reveal: Duration) -> Self {
+        self.reveal = reveal;
+        self
+    }
+
     fn generate_pattern(&mut self) {
         self.pattern.clear();
         self.guesses.clear();
@@ -73,6 +96,34 @@ impl VisualMemoryState {
             since: Instant::now(),
         };
         self.status = format!("Round {} · memorize", self.round);
+        self.peeks_left = 1;
+    }
+
+    /// Shows the pattern again for half the reveal time at the cost of a
+    /// life. Refused when it would take the last life.
+    fn peek(&mut self) {
+        if self.peeks_left == 0 {
+            self.status = "No re-reveals left this round".into();
+            return;
+        }
+        if self.lives <= 1 {
+            self.status = "Not enough lives to re-reveal".into();
+            return;
+        }
+        self.peeks_left -= 1;
+        self.lives -= 1;
+        self.nav.clear();
+        self.phase = Phase::Peek {
+            since: Instant::now(),
+        };
+        self.status = format!("Re-reveal · {} lives left", self.lives);
+    }
+
+    fn recall_status(&self) -> String {
+        format!(
+            "Toggle with space/enter · submit with s · ? re-reveal ({} left, costs a life)",
+            self.peeks_left
+        )
     }
 
     fn toggle(&mut self) {
@@ -89,6 +140,7 @@ impl VisualMemoryState {
             return GameAction::None;
         }
         if self.guesses == self.pattern {
+            self.streak.hit();
             self.status = "Correct".into();
             self.round += 1;
             self.phase = Phase::Result;
@@ -100,19 +152,31 @@ impl VisualMemoryState {
             }
             self.generate_pattern();
         } else {
+            self.streak.miss();
             self.status = "Not quite".into();
             self.lives = self.lives.saturating_sub(1);
             self.phase = Phase::Result;
+            self.round = 1;
             if self.lives == 0 {
-                self.status = format!("Out of lives · best {}", self.best);
+                self.status = format!("Out of lives · best {} · r to restart", self.best);
+                return GameAction::Cue;
             }
-            self.round = 1;
             self.generate_pattern();
         }
         GameAction::None
     }
+}
+
+impl Game for VisualMemoryState {
+    fn kind(&self) -> GameKind {
+        GameKind::VisualMemory
+    }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title("Visual Memory")
             .borders(Borders::ALL)
@@ -125,18 +189,31 @@ impl VisualMemoryState {
             self.round, self.best, self.lives
         ))];
         lines.push(Line::from(self.status.as_str()));
-        for y in 0..GRID {
-            let mut spans = Vec::with_capacity(GRID * 2);
+        let gutter = relative_gutter(self.cursor.1, GRID);
+        for (y, number) in gutter.into_iter().enumerate() {
+            let mut spans = Vec::with_capacity(GRID * 2 + 1);
+            let gutter_style = if y == self.cursor.1 {
+                theme::highlight(Style::default().fg(Color::Yellow))
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(number, gutter_style));
             for x in 0..GRID {
                 let filled = match self.phase {
-                    Phase::Reveal { .. } => self.pattern.contains(&(x, y)),
+                    Phase::Reveal { .. } | Phase::Peek { .. } => self.pattern.contains(&(x, y)),
                     _ => self.guesses.contains(&(x, y)),
                 };
-                let ch = if filled { "■" } else { "·" };
+                let ch = if filled {
+                    theme::glyph("■", "#")
+                } else {
+                    theme::glyph("·", ".")
+                };
                 let style = if (x, y) == self.cursor {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
+                    theme::highlight(
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    )
                 } else {
                     Style::default()
                 };
@@ -148,15 +225,24 @@ impl VisualMemoryState {
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> GameAction {
+    fn handle_event(&mut self, event: &Event) -> GameAction {
         if let Event::Key(key) = event {
-            if self.nav.handle_key(key, &mut self.cursor, GRID, GRID) {
+            let filled = match self.phase {
+                Phase::Reveal { .. } | Phase::Peek { .. } => &self.pattern,
+                _ => &self.guesses,
+            };
+            let occupied = |x, y| filled.contains(&(x, y));
+            if self
+                .nav
+                .handle_key(key, &mut self.cursor, GRID, GRID, Some(&occupied))
+            {
                 return GameAction::None;
             }
 
             match key.code {
                 KeyCode::Char(' ') | KeyCode::Enter => self.toggle(),
                 KeyCode::Char('s') | KeyCode::Char('S') => return self.submit(),
+                KeyCode::Char('?') if matches!(self.phase, Phase::Recall) => self.peek(),
                 KeyCode::Esc => self.nav.clear(),
                 _ => {}
             }
@@ -164,22 +250,82 @@ impl VisualMemoryState {
         GameAction::None
     }
 
-    pub fn handle_tick(&mut self, now: Instant) -> GameAction {
-        if let Phase::Reveal { since } = self.phase {
-            if now.duration_since(since) >= REVEAL {
-                self.phase = Phase::Recall;
-                self.status = "Toggle with space/enter · submit with s".into();
-            }
+    fn handle_tick(&mut self, now: Instant) -> GameAction {
+        let done = match self.phase {
+            Phase::Reveal { since } => now.duration_since(since) >= self.reveal,
+            Phase::Peek { since } => now.duration_since(since) >= self.reveal / 2,
+            _ => false,
+        };
+        if done {
+            self.phase = Phase::Recall;
+            self.status = self.recall_status();
         }
         GameAction::None
     }
 
-    pub fn status_line(&self) -> String {
-        let base = format!("Round {} · Lives {}", self.round, self.lives);
+    fn status_line(&self) -> String {
+        let base = format!(
+            "Round {} · Lives {} · re-reveals {} · streak {}",
+            self.round,
+            self.lives,
+            self.peeks_left,
+            self.streak.current()
+        );
         if let Some(count) = self.nav.prefix() {
             format!("{} · count {}", base, count)
         } else {
             base
         }
     }
+
+    fn shift_clock(&mut self, paused: Duration) {
+        if let Phase::Reveal { since } | Phase::Peek { since } = &mut self.phase {
+            *since += paused;
+        }
+    }
+
+    fn restart_finished(&mut self) -> bool {
+        if self.lives > 0 {
+            return false;
+        }
+        self.lives = 3;
+        self.generate_pattern();
+        true
+    }
+
+    fn end_session(&mut self) -> GameAction {
+        self.streak.take_record(GameKind::VisualMemory)
+    }
+
+    fn is_animating(&self) -> bool {
+        matches!(self.phase, Phase::Reveal { .. } | Phase::Peek { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn re_reveal_costs_a_life_and_returns_to_recall() {
+        let mut state = VisualMemoryState::with_seed(6);
+        state.phase = Phase::Recall;
+        state.handle_event(&Event::Key(KeyEvent::new(
+            KeyCode::Char('?'),
+            KeyModifiers::NONE,
+        )));
+        assert_eq!(state.lives, 2);
+        assert_eq!(state.peeks_left, 0);
+        let Phase::Peek { since } = state.phase else {
+            panic!("the pattern is shown again");
+        };
+        state.handle_tick(since + state.reveal / 2);
+        assert!(matches!(state.phase, Phase::Recall));
+
+        state.peek();
+        assert_eq!(state.lives, 2);
+        assert!(matches!(state.phase, Phase::Recall));
+    }
 }
@@ -1,3 +1,7 @@
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local};
+
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
@@ -5,37 +9,77 @@ use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 pub struct HudContext<'a> {
     pub primary: &'a str,
     pub secondary: &'a str,
-    pub command: Option<&'a str>,
-    pub toast: Option<&'a str>,
+    /// Command palette text and the caret's char index within it.
+    pub command: Option<(&'a str, usize)>,
+    pub toast: Option<(&'a str, ToastKind)>,
+    /// Shown in the local time zone.
+    pub wall_time: Option<SystemTime>,
+    pub session_elapsed: Option<Duration>,
+    /// Practice mode is on and results are not being saved.
+    pub practice: bool,
+    /// Title of the running game, shown as `menu › Title` on the border.
+    pub breadcrumb: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Warn,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> Color {
+        match self {
+            ToastKind::Info => Color::LightGreen,
+            ToastKind::Warn => Color::Yellow,
+            ToastKind::Error => Color::LightRed,
+        }
+    }
 }
 
 pub fn render(frame: &mut Frame, area: Rect, ctx: HudContext<'_>) {
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::TOP)
         .border_style(Style::default().fg(Color::DarkGray));
+    if let Some(title) = ctx.breadcrumb {
+        block = block.title(Span::styled(
+            format!(" menu › {title} "),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    if ctx.practice {
+        block = block.title(Span::styled(
+            " PRACTICE · scores not saved ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
     frame.render_widget(block.clone(), area);
     let inner = block.inner(area);
     let mut text = Vec::new();
-    if !ctx.primary.is_empty() {
-        text.push(Line::from(ctx.primary));
+    let clock = clock_text(ctx.wall_time, ctx.session_elapsed);
+    if !ctx.primary.is_empty() || !clock.is_empty() {
+        text.push(Line::from(right_align(
+            ctx.primary,
+            &clock,
+            inner.width as usize,
+        )));
     }
     if !ctx.secondary.is_empty() {
         text.push(Line::from(ctx.secondary));
     }
 
-    if let Some(command) = ctx.command {
-        text.push(Line::from(Span::styled(
-            command,
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )));
+    if let Some((command, caret)) = ctx.command {
+        text.push(command_line(command, caret));
     }
 
-    if let Some(toast) = ctx.toast {
+    if let Some((toast, kind)) = ctx.toast {
         text.push(Line::from(Span::styled(
             toast,
-            Style::default().fg(Color::LightGreen),
+            Style::default().fg(kind.color()),
         )));
     }
 
@@ -46,3 +90,75 @@ pub fn render(frame: &mut Frame, area: Rect, ctx: HudContext<'_>) {
     let paragraph = Paragraph::new(text).wrap(Wrap { trim: true });
     frame.render_widget(paragraph, inner);
 }
+
+/// `:buffer` with the char under the caret drawn reversed, or a reversed
+/// space when the caret is at the end.
+fn command_line(command: &str, caret: usize) -> Line<'static> {
+    let style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let before: String = command.chars().take(caret).collect();
+    let mut rest = command.chars().skip(caret);
+    let under = rest.next().map(String::from).unwrap_or_else(|| " ".into());
+    let after: String = rest.collect();
+    Line::from(vec![
+        Span::styled(format!(":{before}"), style),
+        Span::styled(under, style.add_modifier(Modifier::REVERSED)),
+        Span::styled(after, style),
+    ])
+}
+
+fn clock_text(wall_time: Option<SystemTime>, session: Option<Duration>) -> String {
+    let mut parts = Vec::new();
+    if let Some(time) = wall_time {
+        parts.push(DateTime::<Local>::from(time).format("%H:%M").to_string());
+    }
+    if let Some(session) = session {
+        let secs = session.as_secs();
+        parts.push(format!(
+            "session {}:{:02}:{:02}",
+            secs / 3_600,
+            (secs / 60) % 60,
+            secs % 60
+        ));
+    }
+    parts.join(" · ")
+}
+
+/// Pads `left` so `right` ends at `width`. When both do not fit, `right` is
+/// dropped so the status text is never clipped by the clock.
+fn right_align(left: &str, right: &str, width: usize) -> String {
+    let left_len = left.chars().count();
+    let right_len = right.chars().count();
+    if right.is_empty() || left_len + 1 + right_len > width {
+        return left.to_string();
+    }
+    format!("{left}{}{right}", " ".repeat(width - left_len - right_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_ends_at_the_right_edge() {
+        let line = right_align("Level 3", "session 0:01:05", 30);
+        assert_eq!(line.chars().count(), 30);
+        assert!(line.starts_with("Level 3 "));
+        assert!(line.ends_with("session 0:01:05"));
+    }
+
+    #[test]
+    fn clock_is_dropped_when_it_does_not_fit() {
+        assert_eq!(right_align("Level 3", "session 0:01:05", 22), "Level 3");
+        assert_eq!(right_align("Level 3", "", 30), "Level 3");
+    }
+
+    #[test]
+    fn session_time_is_hours_minutes_seconds() {
+        assert_eq!(
+            clock_text(None, Some(Duration::from_secs(3_725))),
+            "session 1:02:05"
+        );
+    }
+}
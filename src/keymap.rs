@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crossterm::event::KeyCode;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+static KEYMAP: OnceCell<Keymap> = OnceCell::new();
+
+/// Logical inputs games react to, independent of the physical key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Select,
+    Submit,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = HashMap::from([
+            (Action::MoveLeft, vec![KeyCode::Char('h'), KeyCode::Left]),
+            (Action::MoveRight, vec![KeyCode::Char('l'), KeyCode::Right]),
+            (Action::MoveUp, vec![KeyCode::Char('k'), KeyCode::Up]),
+            (Action::MoveDown, vec![KeyCode::Char('j'), KeyCode::Down]),
+            (Action::Select, vec![KeyCode::Enter, KeyCode::Char(' ')]),
+            (Action::Submit, vec![KeyCode::Enter]),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Reads `{"MoveLeft": ["a", "Left"], ...}` overrides on top of the
+    /// defaults. Unknown key names are skipped; a missing or malformed file
+    /// leaves the defaults untouched.
+    pub fn load(path: &Path) -> Self {
+        let mut keymap = Self::default();
+        let Ok(bytes) = fs::read(path) else {
+            return keymap;
+        };
+        let Ok(overrides) = serde_json::from_slice::<HashMap<Action, Vec<String>>>(&bytes) else {
+            return keymap;
+        };
+        for (action, names) in overrides {
+            let keys: Vec<KeyCode> = names.iter().filter_map(|name| parse_key(name)).collect();
+            if !keys.is_empty() {
+                keymap.bindings.insert(action, keys);
+            }
+        }
+        keymap
+    }
+
+    pub fn is(&self, code: KeyCode, action: Action) -> bool {
+        self.bindings
+            .get(&action)
+            .is_some_and(|keys| keys.contains(&code))
+    }
+}
+
+pub fn install(keymap: Keymap) {
+    let _ = KEYMAP.set(keymap);
+}
+
+/// The keymap installed at startup, or the defaults when none was installed.
+pub fn current() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(ch));
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "enter" => Some(KeyCode::Enter),
+        "space" => Some(KeyCode::Char(' ')),
+        "tab" => Some(KeyCode::Tab),
+        "backspace" => Some(KeyCode::Backspace),
+        "esc" => Some(KeyCode::Esc),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remapped_select_key_replaces_the_default() {
+        let path = std::env::temp_dir().join(format!("arcade-keymap-{}.json", std::process::id()));
+        fs::write(&path, r#"{"Select": ["f", "nonsense"], "MoveLeft": []}"#).unwrap();
+        let keymap = Keymap::load(&path);
+        fs::remove_file(&path).ok();
+
+        assert!(keymap.is(KeyCode::Char('f'), Action::Select));
+        assert!(!keymap.is(KeyCode::Enter, Action::Select));
+        // An empty list keeps the default binding.
+        assert!(keymap.is(KeyCode::Char('h'), Action::MoveLeft));
+        assert!(keymap.is(KeyCode::Enter, Action::Submit));
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::games::{GameKind, StatRecord};
+use crate::menu::best_record;
+
+pub fn render(frame: &mut Frame, area: Rect, stats: &HashMap<GameKind, Vec<StatRecord>>) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs())
+        .unwrap_or(0);
+    let title_width = GameKind::ALL
+        .iter()
+        .map(|kind| kind.title().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{:<title_width$}  {:<16}  Set", "Game", "Best"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for (kind, best) in ranked_bests(stats) {
+        let line = match best {
+            Some(record) => Line::from(format!(
+                "{:<title_width$}  {:<16}  {}",
+                kind.title(),
+                format!("{}: {}", record.label, record.value),
+                format_relative_time(record.recorded_at, now)
+            )),
+            None => Line::from(Span::styled(
+                format!("{:<title_width$}  {:<16}  -", kind.title(), "No score yet"),
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        lines.push(line);
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Press any key to return",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title("High Scores")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Every game paired with its all-time best, most recently improved first.
+/// Games without a score sink to the bottom in menu order.
+pub fn ranked_bests(
+    stats: &HashMap<GameKind, Vec<StatRecord>>,
+) -> Vec<(GameKind, Option<&StatRecord>)> {
+    let mut rows: Vec<_> = GameKind::ALL
+        .iter()
+        .map(|kind| {
+            let best = stats
+                .get(kind)
+                .and_then(|history| best_record(*kind, history));
+            (*kind, best)
+        })
+        .collect();
+    rows.sort_by_key(|(_, best)| std::cmp::Reverse(best.map(|record| record.recorded_at)));
+    rows
+}
+
+pub fn format_relative_time(recorded_at: u64, now: u64) -> String {
+    if recorded_at == 0 {
+        return "unknown".into();
+    }
+    let secs = now.saturating_sub(recorded_at);
+    match secs {
+        0..60 => "just now".into(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_at(label: &str, score: f64, recorded_at: u64) -> StatRecord {
+        StatRecord {
+            recorded_at,
+            ..StatRecord::new(label, score.to_string(), score)
+        }
+    }
+
+    #[test]
+    fn newest_best_is_listed_first() {
+        let stats = HashMap::from([
+            (GameKind::Reaction, vec![set_at("Reaction", 240.0, 1_000)]),
+            (
+                GameKind::Sequence,
+                vec![set_at("Level", 7.0, 500), set_at("Level", 9.0, 2_000)],
+            ),
+        ]);
+        let rows = ranked_bests(&stats);
+        assert_eq!(rows[0].0, GameKind::Sequence);
+        assert_eq!(rows[0].1.map(|record| record.score), Some(9.0));
+        assert_eq!(rows[1].0, GameKind::Reaction);
+        assert!(rows[2..].iter().all(|(_, best)| best.is_none()));
+    }
+}
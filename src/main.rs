@@ -1,15 +1,29 @@
 mod app;
+mod config;
+mod daily;
+mod debug;
+mod errlog;
+mod export;
 mod games;
 mod hud;
+mod keymap;
+mod leaderboard;
 mod menu;
+mod serve;
+mod theme;
+mod totals;
 
+use std::env;
 use std::error::Error;
-use std::io;
+use std::io::{self, IsTerminal, Read};
 use std::panic;
+use std::path::PathBuf;
 
 use app::App;
 use crossterm::ExecutableCommand;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+};
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
@@ -17,27 +31,77 @@ use ratatui::prelude::*;
 
 fn main() -> Result<(), Box<dyn Error>> {
     install_panic_hook();
+    let typing_text = stdin_text()?;
+    let feed = serve_port()?.map(serve::StatsFeed::spawn).transpose()?;
+    if let Some(path) = keymap_path() {
+        keymap::install(keymap::Keymap::load(&path));
+    }
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnterAlternateScreen)?;
     stdout.execute(EnableMouseCapture)?;
+    stdout.execute(EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
-    let result = App::default().run(&mut terminal);
+    let mut app = App::default();
+    if let Some(feed) = feed {
+        app.serve(feed);
+    }
+    if let Some(text) = &typing_text {
+        app.start_typing_text(text);
+    }
+    let result = app.run(&mut terminal);
     disable_raw_mode()?;
     drop(terminal);
     let mut stdout = io::stdout();
     stdout.execute(LeaveAlternateScreen)?;
     stdout.execute(DisableMouseCapture)?;
+    stdout.execute(DisableFocusChange)?;
     result
 }
 
+/// `arcade typing --stdin` reads the Typing prompt from a pipe. Keys are
+/// still read from the terminal, which crossterm opens directly when stdin
+/// is not one.
+fn stdin_text() -> io::Result<Option<String>> {
+    if !env::args().skip(1).any(|arg| arg == "--stdin") {
+        return Ok(None);
+    }
+    let mut stdin = io::stdin();
+    let mut text = String::new();
+    if !stdin.is_terminal() {
+        stdin.read_to_string(&mut text)?;
+    }
+    Ok(Some(text))
+}
+
+/// `--serve <port>` shares the stats as JSON on localhost; off by default.
+fn serve_port() -> Result<Option<u16>, Box<dyn Error>> {
+    let mut args = env::args().skip_while(|arg| arg != "--serve");
+    if args.next().is_none() {
+        return Ok(None);
+    }
+    match args.next().and_then(|port| port.parse().ok()) {
+        Some(port) => Ok(Some(port)),
+        None => Err("--serve needs a port, e.g. --serve 7878".into()),
+    }
+}
+
+fn keymap_path() -> Option<PathBuf> {
+    let mut dir = dirs::config_dir()?;
+    dir.push("bored");
+    dir.push("keymap.json");
+    Some(dir)
+}
+
 fn install_panic_hook() {
     panic::set_hook(Box::new(|info| {
         let _ = disable_raw_mode();
         let mut stdout = io::stdout();
         let _ = stdout.execute(LeaveAlternateScreen);
         let _ = stdout.execute(DisableMouseCapture);
+        let _ = stdout.execute(DisableFocusChange);
+        errlog::append(&format!("panic: {info}"));
         eprintln!("Application crashed: {info}");
     }));
 }
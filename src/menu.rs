@@ -1,40 +1,123 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use serde::{Deserialize, Serialize};
 
-use crate::games::{GameKind, ScoreDirection, StatRecord};
+use crate::games::{GameKind, STREAK_LABEL, ScoreDirection, StatRecord};
+use crate::theme;
+use crate::totals::Totals;
 
-const MAX_CHART_POINTS: usize = 32;
-const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const MIN_CHART_POINTS: usize = 4;
+const TREND_LABEL_WIDTH: usize = 12;
+pub const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const ASCII_SPARKS: [char; 8] = ['_', '.', ',', '-', '~', '=', '*', '#'];
+const BRAILLE_ROWS: usize = 3;
+const BRAILLE_MIN_WIDTH: usize = 20;
+const TREND_WINDOW: usize = 5;
+const TREND_TOLERANCE: f64 = 0.02;
+/// Type-ahead letters further apart than this start a new search.
+const TYPEAHEAD_IDLE: Duration = Duration::from_millis(800);
+/// Bounds on the game list's share of the width, so neither pane vanishes.
+const LIST_PERCENT_MIN: u16 = 20;
+const LIST_PERCENT_MAX: u16 = 80;
 
 #[derive(Debug)]
 pub struct MenuState {
     items: Vec<GameKind>,
+    order: Vec<GameKind>,
     selected: usize,
+    chart_style: ChartStyle,
+    /// Width of the game list as a percentage; the detail pane gets the rest.
+    list_percent: u16,
+    filter: Option<String>,
+    sort: MenuSort,
+    favorites: HashSet<GameKind>,
+    /// Where the game list was last drawn, for mapping mouse clicks to rows.
+    list_area: Rect,
+    /// Letters typed to jump to a title, and when the last one came in.
+    typeahead: String,
+    typeahead_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MenuSort {
+    /// Declaration order.
+    Default,
+    /// Games with a best record above the unplayed ones.
+    Best,
+    /// Most launched first, from `Totals::plays`.
+    Plays,
+    /// Alphabetical by title.
+    Name,
+}
+
+impl MenuSort {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "default" => Some(MenuSort::Default),
+            "best" => Some(MenuSort::Best),
+            "plays" => Some(MenuSort::Plays),
+            "name" => Some(MenuSort::Name),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuSort::Default => "default",
+            MenuSort::Best => "best",
+            MenuSort::Plays => "plays",
+            MenuSort::Name => "name",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartStyle {
+    Sparkline,
+    Braille,
 }
 
 impl Default for MenuState {
     fn default() -> Self {
         Self {
             items: GameKind::ALL.to_vec(),
+            order: GameKind::ALL.to_vec(),
             selected: 0,
+            chart_style: ChartStyle::Sparkline,
+            list_percent: 45,
+            filter: None,
+            sort: MenuSort::Default,
+            favorites: HashSet::new(),
+            list_area: Rect::default(),
+            typeahead: String::new(),
+            typeahead_at: None,
         }
     }
 }
 
 impl MenuState {
-    pub fn selected_kind(&self) -> GameKind {
-        self.items[self.selected]
+    pub fn selected_kind(&self) -> Option<GameKind> {
+        self.items.get(self.selected).copied()
     }
 
     pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         self.selected = (self.selected + 1) % self.items.len();
     }
 
     pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         if self.selected == 0 {
             self.selected = self.items.len() - 1;
         } else {
@@ -42,15 +125,213 @@ impl MenuState {
         }
     }
 
+    /// Jumps to the first title starting with the letters typed so far.
+    /// Repeating one letter cycles through the titles that start with it.
+    pub fn type_ahead(&mut self, ch: char) {
+        let now = Instant::now();
+        if self
+            .typeahead_at
+            .is_none_or(|at| now.duration_since(at) > TYPEAHEAD_IDLE)
+        {
+            self.typeahead.clear();
+        }
+        self.typeahead_at = Some(now);
+        let ch = ch.to_ascii_lowercase();
+        let repeat = !self.typeahead.is_empty() && self.typeahead.chars().all(|c| c == ch);
+        self.typeahead.push(ch);
+        let (prefix, start) = if repeat {
+            (ch.to_string(), self.selected + 1)
+        } else {
+            (self.typeahead.clone(), 0)
+        };
+        let count = self.items.len();
+        if let Some(idx) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&idx| self.items[idx].title().to_lowercase().starts_with(&prefix))
+        {
+            self.selected = idx;
+        }
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    pub fn start_filter(&mut self) {
+        self.filter = Some(String::new());
+        self.apply_filter();
+    }
+
+    pub fn push_filter(&mut self, ch: char) {
+        if let Some(filter) = &mut self.filter {
+            filter.push(ch);
+            self.apply_filter();
+        }
+    }
+
+    pub fn pop_filter(&mut self) {
+        if let Some(filter) = &mut self.filter {
+            filter.pop();
+            self.apply_filter();
+        }
+    }
+
+    pub fn clear_filter(&mut self) {
+        let selected = self.selected_kind();
+        self.filter = None;
+        self.items = self.visible_kinds();
+        self.select_kind(selected);
+    }
+
+    fn apply_filter(&mut self) {
+        self.items = self.visible_kinds();
+        self.selected = 0;
+    }
+
+    /// The sorted order with favorites pulled to the front, narrowed to titles
+    /// containing the filter (case-insensitive).
+    fn visible_kinds(&self) -> Vec<GameKind> {
+        let needle = self.filter.as_deref().unwrap_or_default().to_lowercase();
+        let (mut kinds, rest): (Vec<GameKind>, Vec<GameKind>) = self
+            .order
+            .iter()
+            .copied()
+            .filter(|kind| kind.title().to_lowercase().contains(&needle))
+            .partition(|kind| self.favorites.contains(kind));
+        kinds.extend(rest);
+        kinds
+    }
+
+    /// Toggles the selected game's favorite flag, keeping it selected as it
+    /// moves. Returns the game and whether it is now a favorite.
+    pub fn toggle_favorite(&mut self) -> Option<(GameKind, bool)> {
+        let kind = self.selected_kind()?;
+        let favorite = if self.favorites.remove(&kind) {
+            false
+        } else {
+            self.favorites.insert(kind);
+            true
+        };
+        self.items = self.visible_kinds();
+        self.select_kind(Some(kind));
+        Some((kind, favorite))
+    }
+
+    /// Favorites in menu declaration order, for saving.
+    pub fn favorites(&self) -> Vec<GameKind> {
+        GameKind::ALL
+            .into_iter()
+            .filter(|kind| self.favorites.contains(kind))
+            .collect()
+    }
+
+    pub fn set_favorites(&mut self, kinds: impl IntoIterator<Item = GameKind>) {
+        let selected = self.selected_kind();
+        self.favorites = kinds.into_iter().collect();
+        self.items = self.visible_kinds();
+        self.select_kind(selected);
+    }
+
+    pub fn sort(&self) -> MenuSort {
+        self.sort
+    }
+
+    /// Reorders the menu, keeping the cursor on the same game.
+    pub fn set_sort(
+        &mut self,
+        sort: MenuSort,
+        stats: &HashMap<GameKind, Vec<StatRecord>>,
+        totals: &Totals,
+    ) {
+        let selected = self.selected_kind();
+        self.sort = sort;
+        let mut order = GameKind::ALL.to_vec();
+        match sort {
+            MenuSort::Default => {}
+            MenuSort::Best => order.sort_by_key(|kind| {
+                stats
+                    .get(kind)
+                    .and_then(|history| best_record(*kind, history))
+                    .is_none()
+            }),
+            MenuSort::Plays => order.sort_by_key(|kind| std::cmp::Reverse(totals.plays(*kind))),
+            MenuSort::Name => order.sort_by_key(|kind| kind.title()),
+        }
+        self.order = order;
+        self.items = self.visible_kinds();
+        self.select_kind(selected);
+    }
+
+    fn select_kind(&mut self, kind: Option<GameKind>) {
+        self.selected = kind
+            .and_then(|kind| self.items.iter().position(|item| *item == kind))
+            .unwrap_or(0);
+    }
+
+    pub fn chart_style(&self) -> ChartStyle {
+        self.chart_style
+    }
+
+    pub fn set_chart_style(&mut self, style: ChartStyle) {
+        self.chart_style = style;
+    }
+
+    pub fn set_list_percent(&mut self, percent: u16) {
+        self.list_percent = percent.clamp(LIST_PERCENT_MIN, LIST_PERCENT_MAX);
+    }
+
+    /// Widths of the game list and the detail pane.
+    pub fn pane_constraints(&self) -> [Constraint; 2] {
+        [
+            Constraint::Percentage(self.list_percent),
+            Constraint::Percentage(100 - self.list_percent),
+        ]
+    }
+
+    pub fn toggle_chart_style(&mut self) -> ChartStyle {
+        self.chart_style = match self.chart_style {
+            ChartStyle::Sparkline => ChartStyle::Braille,
+            ChartStyle::Braille => ChartStyle::Sparkline,
+        };
+        self.chart_style
+    }
+
+    /// The list index under a click, or `None` for the border, the detail
+    /// pane or the blank space below the last game.
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = self.list_area.inner(Margin::new(1, 1));
+        if column < inner.x
+            || column >= inner.x + inner.width
+            || row < inner.y
+            || row >= inner.y + inner.height
+        {
+            return None;
+        }
+        let idx = (row - inner.y) as usize;
+        (idx < self.items.len()).then_some(idx)
+    }
+
+    /// Selects the clicked row. Returns true when it was already selected, so
+    /// a second click on the same game launches it.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let Some(idx) = self.row_at(column, row) else {
+            return false;
+        };
+        let again = idx == self.selected;
+        self.selected = idx;
+        again
+    }
+
     pub fn render(
-        &self,
+        &mut self,
         frame: &mut Frame,
         area: Rect,
         stats: &HashMap<GameKind, Vec<StatRecord>>,
+        totals: &Totals,
     ) {
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .constraints(self.pane_constraints())
             .split(area);
 
         let items: Vec<ListItem> = self
@@ -58,54 +339,142 @@ impl MenuState {
             .iter()
             .enumerate()
             .map(|(idx, kind)| {
-                let mut line = format!("{}", kind.title());
-                if let Some(history) = stats.get(kind) {
-                    if let Some(best) = best_record(*kind, history) {
-                        line.push_str(&format!("  · {}: {}", best.label, best.value));
-                    }
+                let mut line = if self.favorites.contains(kind) {
+                    format!("★ {}", kind.title())
+                } else {
+                    kind.title().to_string()
+                };
+                if let Some(history) = stats.get(kind)
+                    && let Some(best) = best_record(*kind, history)
+                {
+                    line.push_str(&format!("  · {}: {}", best.label, best.value));
                 }
                 let style = if idx == self.selected {
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD)
+                    theme::highlight(
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    )
                 } else {
                     Style::default()
                 };
                 ListItem::new(Span::styled(line, style))
             })
             .collect();
-        let list = List::new(items)
+        let list = List::new(items).block(
+            Block::default()
+                .title(match &self.filter {
+                    Some(filter) => format!("Memory Arcade · /{filter}"),
+                    None => "Memory Arcade".to_string(),
+                })
+                .title_bottom(Span::styled(
+                    format!(" {} ", totals.summary()),
+                    Style::default().fg(Color::DarkGray),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Gray)),
+        );
+        frame.render_widget(list, chunks[0]);
+        self.list_area = chunks[0];
+
+        let chart_width = chunks[1].width.saturating_sub(2) as usize;
+        let detail_lines = match self.selected_kind() {
+            Some(kind) => self.detail_lines(kind, stats, chart_width),
+            None => vec![Line::from(format!(
+                "No games match \"{}\"",
+                self.filter.as_deref().unwrap_or_default()
+            ))],
+        };
+
+        let detail = Paragraph::new(detail_lines)
             .block(
                 Block::default()
-                    .title("Memory Arcade")
+                    .title("Details")
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Gray)),
             )
-            .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
-        frame.render_widget(list, chunks[0]);
+            .wrap(Wrap { trim: true });
+        frame.render_widget(detail, chunks[1]);
+    }
 
-        let selected_kind = self.selected_kind();
+    fn detail_lines(
+        &self,
+        kind: GameKind,
+        stats: &HashMap<GameKind, Vec<StatRecord>>,
+        chart_width: usize,
+    ) -> Vec<Line<'static>> {
         let mut detail_lines = vec![
-            Line::from(selected_kind.title()),
+            Line::from(kind.title()),
             Line::from(""),
-            Line::from(selected_kind.blurb()),
+            Line::from(kind.blurb()),
             Line::from(""),
             Line::from("Personal Best"),
         ];
-        let pb = stats
-            .get(&selected_kind)
-            .and_then(|history| best_record(selected_kind, history))
-            .map(|record| format!("{}: {}", record.label, record.value))
-            .unwrap_or_else(|| "No score yet".to_string());
-        detail_lines.push(Line::from(pb));
-
-        if let Some(history) = stats.get(&selected_kind) {
-            if let Some((chart_line, min_score, max_score)) =
-                build_chart_line(history, selected_kind.score_direction())
-            {
+        let bests = stats
+            .get(&kind)
+            .map(|history| label_bests(kind, history))
+            .unwrap_or_default();
+        if bests.is_empty() {
+            detail_lines.push(Line::from("No score yet"));
+        }
+        for (idx, record) in bests.iter().enumerate() {
+            let line = format!("{}: {}", record.label, record.value);
+            detail_lines.push(if idx == 0 {
+                Line::from(line)
+            } else {
+                Line::styled(line, Style::default().fg(Color::DarkGray))
+            });
+        }
+        if let Some(last) = stats.get(&kind).and_then(|history| history.last())
+            && let Some(note) = &last.note
+        {
+            detail_lines.push(Line::styled(
+                format!("Last run {} · {note}", last.value),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        if let Some(all) = stats.get(&kind) {
+            let history = &series(all, series_label(all).unwrap_or_default());
+            let summary = summarize(history, kind.score_direction());
+            if summary.count > 0 {
+                detail_lines.push(Line::from(format!(
+                    "Mean {} · Median {} · Best {} · Runs {}",
+                    format_score(summary.mean),
+                    format_score(summary.median),
+                    format_score(summary.best),
+                    summary.count
+                )));
+            }
+            if let Some((chart_line, min_score, max_score, trend)) = build_chart_line(
+                history,
+                kind.score_direction(),
+                chart_width.saturating_sub(TREND_LABEL_WIDTH),
+            ) {
                 detail_lines.push(Line::from(""));
                 detail_lines.push(Line::from("Score Progress"));
-                detail_lines.push(chart_line);
+                let braille_fits = chart_width >= BRAILLE_MIN_WIDTH;
+                if self.chart_style == ChartStyle::Braille && braille_fits && !theme::ascii() {
+                    detail_lines.extend(build_braille_chart(history, BRAILLE_ROWS, chart_width));
+                    detail_lines.push(Line::from(trend_span(trend)));
+                } else {
+                    let mut chart_spans = chart_line.spans;
+                    chart_spans.push(Span::raw(" "));
+                    chart_spans.push(trend_span(trend));
+                    detail_lines.push(Line::from(chart_spans));
+                    detail_lines.push(Line::from(vec![
+                        Span::styled("oldest → newest", Style::default().fg(Color::DarkGray)),
+                        Span::raw(" · "),
+                        Span::styled("best", Style::default().fg(Color::Yellow)),
+                        Span::raw(" · "),
+                        Span::styled(
+                            "latest",
+                            Style::default()
+                                .fg(Color::Cyan)
+                                .add_modifier(Modifier::UNDERLINED),
+                        ),
+                    ]));
+                }
                 detail_lines.push(Line::from(format!(
                     "Range {} – {} · samples {}",
                     format_score(min_score),
@@ -120,90 +489,298 @@ impl MenuState {
             detail_lines.push(Line::from(""));
             detail_lines.push(Line::from("No attempts logged yet."));
         }
-
-        let detail = Paragraph::new(detail_lines)
-            .block(
-                Block::default()
-                    .title("Details")
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Gray)),
-            )
-            .wrap(Wrap { trim: true });
-        frame.render_widget(detail, chunks[1]);
+        detail_lines
     }
 
     pub fn status_line(&self) -> String {
-        format!(
-            "Menu · j/k or ↑/↓ to move · enter to launch {}",
-            self.selected_kind().title()
-        )
+        if let Some(filter) = &self.filter {
+            return format!(
+                "Filter /{filter} · {} matches · enter to launch · esc to clear",
+                self.items.len()
+            );
+        }
+        match self.selected_kind() {
+            Some(kind) => format!(
+                "Menu · j/k or ↑/↓ to move · type a title's letters to jump · / filter · f favorite · c chart style · enter to launch {}",
+                kind.title()
+            ),
+            None => "Menu · no games".to_string(),
+        }
+    }
+}
+
+/// The label the menu follows for a game: the one its newest run was
+/// recorded under. Other labels come from other modes (a board size, a
+/// base) whose scores do not compare, so bests, summaries and charts only
+/// ever look at one label at a time. Streaks are passed over unless they
+/// are all there is.
+pub fn series_label(history: &[StatRecord]) -> Option<&str> {
+    history
+        .iter()
+        .rev()
+        .find(|record| record.label != STREAK_LABEL)
+        .or(history.last())
+        .map(|record| record.label.as_str())
+}
+
+/// The runs recorded under `label`, oldest first.
+fn series(history: &[StatRecord], label: &str) -> Vec<StatRecord> {
+    history
+        .iter()
+        .filter(|record| record.label == label)
+        .cloned()
+        .collect()
+}
+
+/// The best run under the label the menu follows.
+pub fn best_record(kind: GameKind, history: &[StatRecord]) -> Option<&StatRecord> {
+    let label = series_label(history)?;
+    best_of(kind, history.iter().filter(|record| record.label == label))
+}
+
+/// The best run under every label, the followed one first.
+pub fn label_bests(kind: GameKind, history: &[StatRecord]) -> Vec<&StatRecord> {
+    let mut labels: Vec<&str> = series_label(history).into_iter().collect();
+    for record in history {
+        if !labels.contains(&record.label.as_str()) {
+            labels.push(&record.label);
+        }
     }
+    labels
+        .into_iter()
+        .filter_map(|label| best_of(kind, history.iter().filter(|record| record.label == label)))
+        .collect()
 }
 
-fn best_record<'a>(kind: GameKind, history: &'a [StatRecord]) -> Option<&'a StatRecord> {
+fn best_of<'a>(
+    kind: GameKind,
+    records: impl Iterator<Item = &'a StatRecord>,
+) -> Option<&'a StatRecord> {
+    let by_score =
+        |a: &&StatRecord, b: &&StatRecord| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal);
     match kind.score_direction() {
-        ScoreDirection::HigherIsBetter => history
-            .iter()
-            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal)),
-        ScoreDirection::LowerIsBetter => history
-            .iter()
-            .min_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal)),
+        ScoreDirection::HigherIsBetter => records.max_by(by_score),
+        ScoreDirection::LowerIsBetter => records.min_by(by_score),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub best: f64,
+}
+
+/// Aggregates a game's history; an empty history yields a zeroed summary.
+pub fn summarize(history: &[StatRecord], dir: ScoreDirection) -> Summary {
+    if history.is_empty() {
+        return Summary::default();
+    }
+    let mut scores: Vec<f64> = history.iter().map(|record| record.score).collect();
+    scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let count = scores.len();
+    let mean = scores.iter().sum::<f64>() / count as f64;
+    let median = if count.is_multiple_of(2) {
+        (scores[count / 2 - 1] + scores[count / 2]) / 2.0
+    } else {
+        scores[count / 2]
+    };
+    let best = match dir {
+        ScoreDirection::HigherIsBetter => scores[count - 1],
+        ScoreDirection::LowerIsBetter => scores[0],
+    };
+    Summary {
+        count,
+        mean,
+        median,
+        best,
     }
 }
 
 fn build_chart_line(
     history: &[StatRecord],
     direction: ScoreDirection,
-) -> Option<(Line<'static>, f64, f64)> {
+    width: usize,
+) -> Option<(Line<'static>, f64, f64, Trend)> {
     if history.is_empty() {
         return None;
     }
-    let len = history.len().min(MAX_CHART_POINTS);
-    let slice = &history[history.len() - len..];
-    let min_score = slice
+    let min_score = history
         .iter()
         .fold(f64::INFINITY, |acc, record| acc.min(record.score));
-    let max_score = slice
+    let max_score = history
         .iter()
         .fold(f64::NEG_INFINITY, |acc, record| acc.max(record.score));
+    let points = downsample(history, width.max(MIN_CHART_POINTS));
+    let shown_min = points
+        .iter()
+        .fold(f64::INFINITY, |acc, score| acc.min(*score));
+    let shown_max = points
+        .iter()
+        .fold(f64::NEG_INFINITY, |acc, score| acc.max(*score));
     let best_idx = match direction {
-        ScoreDirection::HigherIsBetter => slice
+        ScoreDirection::HigherIsBetter => points
             .iter()
             .enumerate()
-            .max_by(|(_, a), (_, b)| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
             .map(|(idx, _)| idx)
             .unwrap_or(0),
-        ScoreDirection::LowerIsBetter => slice
+        ScoreDirection::LowerIsBetter => points
             .iter()
             .enumerate()
-            .min_by(|(_, a), (_, b)| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
             .map(|(idx, _)| idx)
             .unwrap_or(0),
     };
-    let range = (max_score - min_score).abs();
-    let spans: Vec<Span> = slice
+    let range = (shown_max - shown_min).abs();
+    let spans: Vec<Span> = points
         .iter()
         .enumerate()
-        .map(|(idx, record)| {
+        .map(|(idx, score)| {
             let normalized = if range < f64::EPSILON {
                 0.5
             } else {
-                ((record.score - min_score) / (max_score - min_score)).clamp(0.0, 1.0)
+                ((score - shown_min) / (shown_max - shown_min)).clamp(0.0, 1.0)
             };
             let bucket = (normalized * (SPARKS.len() - 1) as f64).round() as usize;
             let bucket = bucket.min(SPARKS.len() - 1);
-            let ch = SPARKS[bucket];
-            let style = if idx == best_idx {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
+            let ch = if theme::ascii() {
+                ASCII_SPARKS[bucket]
             } else {
-                Style::default().fg(Color::Gray)
+                SPARKS[bucket]
+            };
+            let newest = idx + 1 == points.len();
+            let style = match (idx == best_idx, newest) {
+                // A new best keeps the best color and gains the newest underline.
+                (true, true) => Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                (true, false) => Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                (false, true) => Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::UNDERLINED),
+                (false, false) => Style::default().fg(Color::Gray),
             };
             Span::styled(ch.to_string(), style)
         })
         .collect();
-    Some((Line::from(spans), min_score, max_score))
+    let trend = trend(history, direction);
+    Some((Line::from(spans), min_score, max_score, trend))
+}
+
+/// Scores to plot across `points` columns: the raw history when it fits,
+/// otherwise the mean of each evenly sized bucket.
+fn downsample(history: &[StatRecord], points: usize) -> Vec<f64> {
+    if history.len() <= points || points == 0 {
+        return history.iter().map(|record| record.score).collect();
+    }
+    (0..points)
+        .map(|bucket| {
+            let start = bucket * history.len() / points;
+            let end = ((bucket + 1) * history.len() / points).max(start + 1);
+            let slice = &history[start..end];
+            slice.iter().map(|record| record.score).sum::<f64>() / slice.len() as f64
+        })
+        .collect()
+}
+
+/// Draws the score history as a connected line of braille dots: each cell packs
+/// two samples side by side and `rows` cells stack to give `rows * 4` levels.
+fn build_braille_chart(history: &[StatRecord], rows: usize, width: usize) -> Vec<Line<'static>> {
+    const LEFT: [u8; 4] = [0x01, 0x02, 0x04, 0x40];
+    const RIGHT: [u8; 4] = [0x08, 0x10, 0x20, 0x80];
+
+    let slice = downsample(history, width.max(MIN_CHART_POINTS) * 2);
+    if slice.is_empty() || rows == 0 {
+        return Vec::new();
+    }
+    let min_score = slice
+        .iter()
+        .fold(f64::INFINITY, |acc, score| acc.min(*score));
+    let max_score = slice
+        .iter()
+        .fold(f64::NEG_INFINITY, |acc, score| acc.max(*score));
+    let range = max_score - min_score;
+    let levels = rows * 4;
+    let level_of = |score: f64| {
+        if range < f64::EPSILON {
+            levels / 2
+        } else {
+            (((score - min_score) / range) * (levels - 1) as f64).round() as usize
+        }
+    };
+
+    let cols = slice.len().div_ceil(2);
+    let mut cells = vec![vec![0u8; cols]; rows];
+    let mut previous = None;
+    for (idx, score) in slice.iter().enumerate() {
+        let level = level_of(*score).min(levels - 1);
+        let (low, high) = match previous {
+            Some(prev) if prev < level => (prev + 1, level),
+            Some(prev) if prev > level => (level, prev - 1),
+            _ => (level, level),
+        };
+        let bits = if idx % 2 == 0 { LEFT } else { RIGHT };
+        for dot_level in low..=high {
+            let from_top = levels - 1 - dot_level;
+            cells[from_top / 4][idx / 2] |= bits[from_top % 4];
+        }
+        previous = Some(level);
+    }
+
+    cells
+        .into_iter()
+        .map(|row| {
+            let text: String = row
+                .into_iter()
+                .map(|bits| char::from_u32(0x2800 + bits as u32).unwrap_or(' '))
+                .collect();
+            Line::from(Span::styled(text, Style::default().fg(Color::Gray)))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trend {
+    Improving,
+    Declining,
+    Flat,
+}
+
+/// Compares the mean of the most recent window against the window before it.
+/// Short histories split what they have in half; a single sample is flat.
+fn trend(history: &[StatRecord], direction: ScoreDirection) -> Trend {
+    let window = TREND_WINDOW.min(history.len() / 2);
+    if window == 0 {
+        return Trend::Flat;
+    }
+    let mean = |records: &[StatRecord]| {
+        records.iter().map(|record| record.score).sum::<f64>() / records.len() as f64
+    };
+    let recent = mean(&history[history.len() - window..]);
+    let prior = mean(&history[history.len() - 2 * window..history.len() - window]);
+    let delta = recent - prior;
+    if delta.abs() <= prior.abs().max(f64::EPSILON) * TREND_TOLERANCE {
+        return Trend::Flat;
+    }
+    let rising = delta > 0.0;
+    match (direction, rising) {
+        (ScoreDirection::HigherIsBetter, true) | (ScoreDirection::LowerIsBetter, false) => {
+            Trend::Improving
+        }
+        _ => Trend::Declining,
+    }
+}
+
+fn trend_span(trend: Trend) -> Span<'static> {
+    match trend {
+        Trend::Improving => Span::styled("↑ improving", theme::correct()),
+        Trend::Declining => Span::styled("↓ declining", theme::incorrect()),
+        Trend::Flat => Span::styled("→ steady", Style::default().fg(Color::Gray)),
+    }
 }
 
 fn format_score(value: f64) -> String {
@@ -213,3 +790,297 @@ fn format_score(value: f64) -> String {
         format!("{value:.1}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(scores: &[f64]) -> Vec<StatRecord> {
+        scores
+            .iter()
+            .map(|&score| StatRecord::new("Score", score.to_string(), score))
+            .collect()
+    }
+
+    #[test]
+    fn median_of_odd_and_even_counts() {
+        let odd = summarize(&runs(&[5.0, 1.0, 3.0]), ScoreDirection::HigherIsBetter);
+        assert_eq!(odd.median, 3.0);
+        assert_eq!(odd.best, 5.0);
+        assert_eq!(odd.count, 3);
+
+        let even = summarize(&runs(&[4.0, 1.0, 3.0, 2.0]), ScoreDirection::LowerIsBetter);
+        assert_eq!(even.median, 2.5);
+        assert_eq!(even.mean, 2.5);
+        assert_eq!(even.best, 1.0);
+    }
+
+    #[test]
+    fn empty_and_single_histories() {
+        assert_eq!(
+            summarize(&[], ScoreDirection::HigherIsBetter),
+            Summary::default()
+        );
+        let single = summarize(&runs(&[7.0]), ScoreDirection::LowerIsBetter);
+        assert_eq!((single.mean, single.median, single.best), (7.0, 7.0, 7.0));
+    }
+
+    fn run(label: &str, score: f64) -> StatRecord {
+        StatRecord::new(label, score.to_string(), score)
+    }
+
+    #[test]
+    fn bests_never_mix_labels() {
+        let history = vec![
+            run("Digits(bin)", 20.0),
+            run("Digits", 8.0),
+            run("Digits(bin)", 12.0),
+            run("Digits", 6.0),
+        ];
+        let best = best_record(GameKind::NumberMemory, &history).unwrap();
+        assert_eq!((best.label.as_str(), best.score), ("Digits", 8.0));
+        let bests: Vec<_> = label_bests(GameKind::NumberMemory, &history)
+            .into_iter()
+            .map(|record| (record.label.as_str(), record.score))
+            .collect();
+        assert_eq!(bests, [("Digits", 8.0), ("Digits(bin)", 20.0)]);
+        assert_eq!(series(&history, "Digits").len(), 2);
+    }
+
+    #[test]
+    fn endless_accuracy_does_not_replace_a_lives_best() {
+        let history = vec![run("Score", 12.0), run("Accuracy", 96.0), run("Score", 5.0)];
+        let best = best_record(GameKind::VerbalMemory, &history).unwrap();
+        assert_eq!((best.label.as_str(), best.score), ("Score", 12.0));
+        let summary = summarize(
+            &series(&history, "Score"),
+            GameKind::VerbalMemory.score_direction(),
+        );
+        assert_eq!((summary.count, summary.best), (2, 12.0));
+
+        // After an endless run the menu follows accuracy, and the lives best
+        // is still listed under its own label.
+        let mut history = history;
+        history.push(run("Accuracy", 90.0));
+        let bests: Vec<_> = label_bests(GameKind::VerbalMemory, &history)
+            .into_iter()
+            .map(|record| (record.label.as_str(), record.score))
+            .collect();
+        assert_eq!(bests, [("Accuracy", 96.0), ("Score", 12.0)]);
+    }
+
+    #[test]
+    fn small_board_times_stay_out_of_the_standard_best() {
+        let history = vec![
+            run("Total", 9000.0),
+            run("Total (small)", 2000.0),
+            run("Total", 8000.0),
+        ];
+        let best = best_record(GameKind::AimTrainer, &history).unwrap();
+        assert_eq!((best.label.as_str(), best.score), ("Total", 8000.0));
+    }
+
+    #[test]
+    fn streaks_stay_out_of_the_level_best() {
+        let history = vec![
+            run("Level", 9.0),
+            run("Level", 6.0),
+            run(STREAK_LABEL, 15.0),
+        ];
+        assert_eq!(series_label(&history), Some("Level"));
+        let best = best_record(GameKind::ChimpTest, &history).unwrap();
+        assert_eq!((best.label.as_str(), best.score), ("Level", 9.0));
+        assert_eq!(series(&history, "Level").len(), 2);
+
+        let only_streaks = vec![run(STREAK_LABEL, 4.0)];
+        assert_eq!(series_label(&only_streaks), Some(STREAK_LABEL));
+    }
+
+    #[test]
+    fn weighted_aim_scores_stay_apart_from_total_times() {
+        let history = vec![
+            run("Total", 9000.0),
+            run("WeightedScore", 120.0),
+            run("Total", 8500.0),
+        ];
+        let best = best_record(GameKind::AimTrainer, &history).unwrap();
+        assert_eq!((best.label.as_str(), best.score), ("Total", 8500.0));
+        let summary = summarize(
+            &series(&history, "Total"),
+            GameKind::AimTrainer.score_direction(),
+        );
+        assert_eq!(summary.mean, 8750.0);
+    }
+
+    #[test]
+    fn chart_fits_a_narrow_pane() {
+        let history = runs(&(0..40).map(f64::from).collect::<Vec<_>>());
+        let (line, ..) = build_chart_line(&history, ScoreDirection::HigherIsBetter, 10).unwrap();
+        assert!(line.spans.len() <= 10);
+
+        let (tiny, ..) = build_chart_line(&history, ScoreDirection::HigherIsBetter, 1).unwrap();
+        assert_eq!(tiny.spans.len(), MIN_CHART_POINTS);
+    }
+
+    #[test]
+    fn trend_follows_the_score_direction() {
+        let rising = runs(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+        assert_eq!(
+            trend(&rising, ScoreDirection::HigherIsBetter),
+            Trend::Improving
+        );
+        assert_eq!(
+            trend(&rising, ScoreDirection::LowerIsBetter),
+            Trend::Declining
+        );
+
+        let falling = runs(&[300.0, 280.0, 260.0]);
+        assert_eq!(
+            trend(&falling, ScoreDirection::LowerIsBetter),
+            Trend::Improving
+        );
+
+        let steady = runs(&[50.0, 50.5, 49.5, 50.0, 50.2, 49.8]);
+        assert_eq!(trend(&steady, ScoreDirection::HigherIsBetter), Trend::Flat);
+        assert_eq!(
+            trend(&runs(&[7.0]), ScoreDirection::HigherIsBetter),
+            Trend::Flat
+        );
+    }
+
+    #[test]
+    fn rising_scores_draw_an_upward_braille_line() {
+        let history = runs(&(0..8).map(f64::from).collect::<Vec<_>>());
+        let lines = build_braille_chart(&history, 1, 4);
+        let text: Vec<u32> = lines[0].spans[0]
+            .content
+            .chars()
+            .map(|ch| ch as u32 - 0x2800)
+            .collect();
+        assert_eq!(text.len(), 4);
+        // Lowest left dot first, highest right dot last.
+        assert_eq!(text[0] & 0x40, 0x40);
+        assert_eq!(text[3] & 0x08, 0x08);
+        assert_eq!(text[0] & 0x08, 0);
+        assert_eq!(text[3] & 0x40, 0);
+    }
+
+    #[test]
+    fn filter_matches_titles_ignoring_case() {
+        let mut menu = MenuState::default();
+        menu.start_filter();
+        for ch in "MeM".chars() {
+            menu.push_filter(ch);
+        }
+        assert_eq!(
+            menu.items,
+            vec![
+                GameKind::Sequence,
+                GameKind::NumberMemory,
+                GameKind::VerbalMemory,
+                GameKind::VisualMemory,
+            ]
+        );
+        menu.next();
+        menu.clear_filter();
+        assert_eq!(menu.items.len(), GameKind::ALL.len());
+        assert_eq!(menu.selected_kind(), Some(GameKind::NumberMemory));
+    }
+
+    #[test]
+    fn name_sort_orders_titles_and_keeps_the_selection() {
+        let mut menu = MenuState::default();
+        menu.next();
+        menu.set_sort(MenuSort::Name, &HashMap::new(), &Totals::default());
+        let titles: Vec<&str> = menu.items.iter().map(|kind| kind.title()).collect();
+        let mut sorted = titles.clone();
+        sorted.sort();
+        assert_eq!(titles, sorted);
+        assert_eq!(titles[0], "Aim Trainer");
+        assert_eq!(menu.selected_kind(), Some(GameKind::Sequence));
+    }
+
+    #[test]
+    fn plays_sort_counts_launches_not_records() {
+        let mut totals = Totals::default();
+        for _ in 0..5 {
+            totals.record_launch(GameKind::Rhythm);
+        }
+        totals.record_launch(GameKind::ChimpTest);
+        let record = StatRecord::new("Level", "9", 9.0);
+        let stats = HashMap::from([(GameKind::Sequence, vec![record; 3])]);
+        let mut menu = MenuState::default();
+        menu.set_sort(MenuSort::Plays, &stats, &totals);
+        assert_eq!(
+            menu.items[..3],
+            [GameKind::Rhythm, GameKind::ChimpTest, GameKind::Reaction]
+        );
+    }
+
+    #[test]
+    fn favoriting_the_third_game_moves_it_to_the_top() {
+        let mut menu = MenuState::default();
+        menu.next();
+        menu.next();
+        assert_eq!(menu.selected_kind(), Some(GameKind::AimTrainer));
+        assert_eq!(menu.toggle_favorite(), Some((GameKind::AimTrainer, true)));
+        assert_eq!(menu.items[0], GameKind::AimTrainer);
+        assert_eq!(menu.selected, 0);
+        assert_eq!(&menu.items[1..3], &[GameKind::Reaction, GameKind::Sequence]);
+    }
+
+    #[test]
+    fn clicks_map_to_list_rows() {
+        let mut menu = MenuState {
+            list_area: Rect::new(0, 2, 30, 20),
+            ..MenuState::default()
+        };
+        // The border takes the first row and column.
+        assert_eq!(menu.row_at(5, 3), Some(0));
+        assert_eq!(menu.row_at(5, 6), Some(3));
+        assert_eq!(menu.row_at(0, 6), None);
+        assert_eq!(menu.row_at(40, 6), None);
+        assert_eq!(menu.row_at(5, 3 + GameKind::ALL.len() as u16), None);
+
+        assert!(!menu.click(5, 6));
+        assert_eq!(menu.selected_kind(), Some(GameKind::NumberMemory));
+        assert!(menu.click(5, 6));
+    }
+
+    #[test]
+    fn newest_sample_is_marked_without_hiding_a_new_best() {
+        let history = runs(&[5.0, 9.0, 3.0, 4.0]);
+        let (line, ..) = build_chart_line(&history, ScoreDirection::HigherIsBetter, 4).unwrap();
+        let last = line.spans.last().unwrap().style;
+        assert_eq!(last.fg, Some(Color::Cyan));
+        assert!(last.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(
+            !line.spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+
+        let history = runs(&[5.0, 4.0, 3.0, 9.0]);
+        let (line, ..) = build_chart_line(&history, ScoreDirection::HigherIsBetter, 4).unwrap();
+        let last = line.spans.last().unwrap().style;
+        assert_eq!(last.fg, Some(Color::Yellow));
+        assert!(last.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn repeated_letter_cycles_through_matching_titles() {
+        let mut menu = MenuState::default();
+        menu.type_ahead('t');
+        assert_eq!(menu.selected_kind(), Some(GameKind::Typing));
+        menu.type_ahead('t');
+        assert_eq!(menu.selected_kind(), Some(GameKind::TypingAccuracy));
+        menu.type_ahead('t');
+        assert_eq!(menu.selected_kind(), Some(GameKind::Typing));
+
+        let mut menu = MenuState::default();
+        menu.type_ahead('v');
+        menu.type_ahead('i');
+        assert_eq!(menu.selected_kind(), Some(GameKind::VisualMemory));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::export;
+use crate::games::{GameKind, StatRecord};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The latest stats in the `:export-json` schema, served to GETs on
+/// `127.0.0.1:<port>` for stream overlays. Started with `--serve <port>`.
+#[derive(Clone)]
+pub struct StatsFeed {
+    body: Arc<Mutex<Vec<u8>>>,
+}
+
+impl StatsFeed {
+    /// Binds the port and answers requests on a background thread. Binding
+    /// happens up front so a taken port is reported before the UI starts.
+    pub fn spawn(port: u16) -> io::Result<Self> {
+        Ok(Self::serve(TcpListener::bind(("127.0.0.1", port))?))
+    }
+
+    fn serve(listener: TcpListener) -> Self {
+        let feed = Self {
+            body: Arc::new(Mutex::new(b"{}".to_vec())),
+        };
+        let shared = feed.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = shared.respond(stream);
+            }
+        });
+        feed
+    }
+
+    pub fn publish(&self, stats: &HashMap<GameKind, Vec<StatRecord>>) {
+        if let Ok((json, _)) = export::to_json(stats)
+            && let Ok(mut body) = self.body.lock()
+        {
+            *body = json;
+        }
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+        // Drain the headers so the client sees a clean close.
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 2 {
+            header.clear();
+        }
+        if !request.starts_with("GET ") {
+            return stream.write_all(
+                b"HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+        let body = self
+            .body
+            .lock()
+            .map(|body| body.clone())
+            .unwrap_or_default();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+            body.len()
+        )?;
+        stream.write_all(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    fn request(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn get_serves_the_export_document() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let feed = StatsFeed::serve(listener);
+        let mut record = StatRecord::new("Level", "9", 9.0);
+        record.recorded_at = 1_700_000_000;
+        feed.publish(&HashMap::from([(GameKind::Sequence, vec![record])]));
+
+        let response = request(
+            addr,
+            "GET / HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n",
+        );
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let mut lines = head.lines();
+        assert_eq!(lines.next(), Some("HTTP/1.1 200 OK"));
+        let headers: Vec<&str> = lines.collect();
+        assert!(headers.contains(&"Content-Type: application/json"));
+        assert!(headers.contains(&format!("Content-Length: {}", body.len()).as_str()));
+
+        let value: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(value["version"], 1);
+        let sequence = value["games"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|game| game["slug"] == "sequence")
+            .unwrap();
+        assert_eq!(sequence["title"], "Sequence Memory");
+        assert_eq!(sequence["better"], "higher");
+        assert_eq!(sequence["records"][0]["value"], "9");
+        assert_eq!(
+            sequence["records"][0]["recorded_at"],
+            "2023-11-14T22:13:20Z"
+        );
+    }
+
+    #[test]
+    fn other_methods_are_refused() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        StatsFeed::serve(listener);
+        let response = request(addr, "POST / HTTP/1.1\r\nContent-Length: 0\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+static PALETTE: AtomicU8 = AtomicU8::new(Palette::Default as u8);
+static ASCII: AtomicBool = AtomicBool::new(false);
+static DIM: AtomicBool = AtomicBool::new(false);
+
+/// Colors used for right/wrong feedback. Wrong answers are always
+/// underlined as well, so correctness never depends on color alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Palette {
+    /// Green for correct, red for wrong.
+    Default,
+    /// Blue for correct, orange for wrong.
+    Colorblind,
+    /// No colors at all: wrong is underlined and reversed, and every
+    /// colored highlight is drawn in reverse video instead.
+    Mono,
+}
+
+impl Palette {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "default" => Some(Palette::Default),
+            "cb" | "colorblind" => Some(Palette::Colorblind),
+            "mono" | "monochrome" => Some(Palette::Mono),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Default => "default",
+            Palette::Colorblind => "colorblind",
+            Palette::Mono => "mono",
+        }
+    }
+}
+
+pub fn set_palette(palette: Palette) {
+    PALETTE.store(palette as u8, Ordering::Relaxed);
+}
+
+pub fn palette() -> Palette {
+    match PALETTE.load(Ordering::Relaxed) {
+        value if value == Palette::Colorblind as u8 => Palette::Colorblind,
+        value if value == Palette::Mono as u8 => Palette::Mono,
+        _ => Palette::Default,
+    }
+}
+
+/// Strips every color from `area` for the mono palette. A cell that was
+/// picked out by its background is reversed instead, so cursors and
+/// highlights still stand out.
+pub fn mono_buffer(buf: &mut Buffer, area: Rect) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buf.get_mut(x, y);
+            if cell.bg != Color::Reset {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
+}
+
+/// Draws grids and charts with plain ASCII, for terminals missing the
+/// block, braille or symbol glyphs.
+pub fn set_ascii(on: bool) {
+    ASCII.store(on, Ordering::Relaxed);
+}
+
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// `unicode`, or its `ascii` stand-in in ASCII-only mode.
+pub fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if self::ascii() { ascii } else { unicode }
+}
+
+/// Low-light mode: the whole screen is drawn at reduced intensity.
+pub fn set_dim(on: bool) {
+    DIM.store(on, Ordering::Relaxed);
+}
+
+pub fn dim() -> bool {
+    DIM.load(Ordering::Relaxed)
+}
+
+/// Dims every cell in `area` except those with a background color or
+/// reversed video, which are the cursors and highlights and have to stay
+/// easy to find.
+pub fn dim_buffer(buf: &mut Buffer, area: Rect) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buf.get_mut(x, y);
+            if cell.bg == Color::Reset && !cell.modifier.contains(Modifier::REVERSED) {
+                cell.modifier.insert(Modifier::DIM);
+            }
+        }
+    }
+}
+
+/// Style for a cursor or selection. Dim mode draws it in reverse video as
+/// well, so `dim_buffer` leaves it at full intensity.
+pub fn highlight(style: Style) -> Style {
+    if dim() {
+        style.add_modifier(Modifier::REVERSED)
+    } else {
+        style
+    }
+}
+
+pub fn correct() -> Style {
+    correct_in(palette())
+}
+
+pub fn incorrect() -> Style {
+    incorrect_in(palette())
+}
+
+fn correct_in(palette: Palette) -> Style {
+    match palette {
+        Palette::Default => Style::default().fg(Color::Green),
+        Palette::Colorblind => Style::default().fg(Color::LightBlue),
+        Palette::Mono => Style::default(),
+    }
+}
+
+fn incorrect_in(palette: Palette) -> Style {
+    let style = Style::default().add_modifier(Modifier::UNDERLINED);
+    match palette {
+        Palette::Default => style.fg(Color::Red),
+        Palette::Colorblind => style.fg(Color::Indexed(208)).add_modifier(Modifier::BOLD),
+        Palette::Mono => style.add_modifier(Modifier::REVERSED),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answers_are_underlined_in_every_palette() {
+        for palette in [Palette::Default, Palette::Colorblind, Palette::Mono] {
+            assert!(
+                incorrect_in(palette)
+                    .add_modifier
+                    .contains(Modifier::UNDERLINED)
+            );
+            assert!(
+                !correct_in(palette)
+                    .add_modifier
+                    .contains(Modifier::UNDERLINED)
+            );
+        }
+        assert_ne!(
+            incorrect_in(Palette::Colorblind).fg,
+            incorrect_in(Palette::Default).fg
+        );
+    }
+
+    #[test]
+    fn mono_mode_strips_colors_and_reverses_backgrounds() {
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buf = Buffer::empty(area);
+        buf.get_mut(0, 0).set_fg(Color::Green);
+        buf.get_mut(1, 0).set_bg(Color::Red);
+        mono_buffer(&mut buf, area);
+        for x in 0..2 {
+            assert_eq!(buf.get(x, 0).fg, Color::Reset);
+            assert_eq!(buf.get(x, 0).bg, Color::Reset);
+        }
+        assert!(!buf.get(0, 0).modifier.contains(Modifier::REVERSED));
+        assert!(buf.get(1, 0).modifier.contains(Modifier::REVERSED));
+    }
+}
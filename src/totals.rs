@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use dirs::config_dir;
+use serde::{Deserialize, Serialize};
+
+use crate::games::GameKind;
+
+/// Lifetime counters across every game, kept in `totals.json` beside the
+/// scores. Missing fields default to zero so older files keep loading.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Totals {
+    /// Games started, counting restarts and replays.
+    pub launches: u64,
+    /// Launches per game, by its `scores.json` key. Backs `:sort plays`.
+    pub plays: BTreeMap<String, u64>,
+    /// Time spent with a game open, in milliseconds.
+    pub play_ms: u64,
+}
+
+impl Totals {
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(self) {
+            let _ = fs::write(path, json);
+        }
+    }
+
+    pub fn record_launch(&mut self, kind: GameKind) {
+        self.launches += 1;
+        *self
+            .plays
+            .entry(kind.persist_key().to_string())
+            .or_default() += 1;
+    }
+
+    pub fn plays(&self, kind: GameKind) -> u64 {
+        self.plays.get(kind.persist_key()).copied().unwrap_or(0)
+    }
+
+    pub fn add_play_time(&mut self, played: Duration) {
+        self.play_ms = self.play_ms.saturating_add(played.as_millis() as u64);
+    }
+
+    /// "42 games · 3h 05m played".
+    pub fn summary(&self) -> String {
+        let mins = self.play_ms / 60_000;
+        format!(
+            "{} games · {}h {:02}m played",
+            self.launches,
+            mins / 60,
+            mins % 60
+        )
+    }
+}
+
+pub fn totals_path() -> Option<PathBuf> {
+    let mut dir = config_dir()?;
+    dir.push("bored");
+    dir.push("totals.json");
+    Some(dir)
+}